@@ -2,6 +2,8 @@
 
 use std::str::FromStr;
 
+use valust::error::transform::Rejected;
+
 /// Parses a string slice into the specified type `F`.
 ///
 /// ```rust
@@ -52,3 +54,27 @@ pub fn into<F: Into<T>, T>(f: F) -> T {
 pub fn try_into<F: TryInto<T>, T>(f: F) -> Result<T, F::Error> {
     f.try_into()
 }
+
+/// Turns a by-ref fallible function into a transformer that hands its input back on failure.
+///
+/// Use it with `try_ret` to avoid cloning the input on the success path.
+///
+/// ```rust
+/// # use valust_utils::convert::by_ref;
+/// # use valust::{Validate, Raw, error::display::ErrorDisplay};
+/// # use valust_derive::Valust;
+/// #
+/// #[derive(Valust)]
+/// struct Stringify {
+///     #[trans(func(String => try_ret(by_ref(|s: &String| s.parse::<i32>()))))]
+///     num: i32,
+/// }
+///
+/// let err = Stringify::validate(RawStringify { num: "1a".to_owned() }).map(|_| ()).unwrap_err();
+/// assert_eq!(err.transforms[0].value, "(String) \"1a\"");
+/// ```
+pub fn by_ref<I, O, E>(
+    f: impl Fn(&I) -> Result<O, E>,
+) -> impl Fn(I) -> Result<O, Rejected<I, E>> {
+    move |i| f(&i).map_err(|cause| Rejected::new(i, cause))
+}
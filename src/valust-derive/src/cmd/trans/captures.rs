@@ -0,0 +1,127 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::{Expr, Ident, LitStr, Token, Type, parse_quote};
+
+use super::{TransCommand, TransHandler};
+use crate::utils::check_regex::check_regex_lit;
+
+/// `captures(<regex> => <Type>)`, building a `valust::captures::FromCaptures`
+/// type from the named groups of a regex.
+pub struct CapturesCommand;
+
+impl TransCommand for CapturesCommand {
+    fn ident(&self) -> &'static str {
+        "captures"
+    }
+
+    fn parse_inner(&self, tt: ParseStream) -> syn::Result<Box<dyn TransHandler>> {
+        if cfg!(not(any(
+            feature = "regex",
+            feature = "regex-lite",
+            feature = "fancy-regex"
+        ))) {
+            return Err(syn::Error::new(
+                tt.span(),
+                "none of the features `regex`, `regex-lite` and `fancy-regex` is enabled",
+            ));
+        }
+
+        let content;
+        syn::parenthesized!(content in tt);
+        let regex = content.parse::<Expr>()?;
+        let groups = check_regex_lit(&regex)?;
+        content.parse::<Token![=>]>()?;
+        let out_type = content.parse()?;
+        let message = if content.peek(Token![,]) {
+            content.parse::<Token![,]>()?;
+            Some(content.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Box::new(CapturesHandler {
+            regex,
+            groups,
+            out_type,
+            message,
+        }))
+    }
+}
+
+struct CapturesHandler {
+    regex: Expr,
+    /// Named groups of a string literal regex.
+    groups: Option<Vec<String>>,
+    out_type: Type,
+    message: Option<LitStr>,
+}
+
+impl TransHandler for CapturesHandler {
+    fn in_type(&self) -> Option<Type> {
+        Some(parse_quote!(::valust::__private::String))
+    }
+
+    fn out_type(&self) -> Option<Type> {
+        Some(self.out_type.clone())
+    }
+
+    fn gen_transformer_expr(&self, field: &Ident) -> TokenStream {
+        let (regex, out_type) = (&self.regex, &self.out_type);
+        let regex_name =
+            format_ident!("valust_trans_regex_{}", field, span = regex.span());
+
+        // every group read by the type must be in a literal regex
+        let check_groups = self.groups.as_ref().map(|groups| {
+            let target = out_type.to_token_stream().to_string();
+            quote_spanned! {regex.span()=>
+                const _: () = if let ::core::option::Option::Some(message) =
+                    ::valust::__private::check_groups(
+                        <#out_type as ::valust::captures::FromCaptures>::GROUPS,
+                        &[#(#groups),*],
+                        #target,
+                    )
+                    .message()
+                {
+                    ::core::panic!("{}", message)
+                };
+            }
+        });
+
+        quote! {{
+            #check_groups
+            static #regex_name: ::valust::__private::CachedRegex =
+                ::valust::__private::CachedRegex::new(|| ::valust::__private::Regex::new(#regex));
+            ::valust::__private::parse_captures::<#out_type>(
+                &#regex_name,
+                ::core::convert::AsRef::<str>::as_ref(&#field),
+            )
+        }}
+    }
+
+    fn gen_expr_display(&self) -> Option<String> {
+        Some(format!(
+            "captures({} => {})",
+            self.regex.to_token_stream(),
+            self.out_type.to_token_stream()
+        ))
+    }
+
+    /// The message of a value not matching the regex, failing groups are
+    /// reported with the error of their type.
+    fn message(&self, label: &str) -> Option<String> {
+        Some(self.message.as_ref().map_or_else(
+            || format!("{} does not match the regex", label),
+            LitStr::value,
+        ))
+    }
+
+    fn is_fallible(&self) -> bool {
+        true
+    }
+
+    fn parses_captures(&self) -> bool {
+        true
+    }
+}
@@ -3,7 +3,7 @@ use quote::quote;
 use syn::parse::ParseStream;
 use syn::{Expr, Ident, LitStr, Token, Type};
 
-use super::{TransCommand, TransHandler};
+use super::{TransCommand, TransHandler, peek_try_ret};
 
 pub struct ExprCommand;
 
@@ -15,7 +15,9 @@ impl TransCommand for ExprCommand {
     fn parse_inner(&self, tt: ParseStream) -> syn::Result<Box<dyn TransHandler>> {
         let content;
         syn::parenthesized!(content in tt);
-        let in_type = {
+        let in_type = if peek_try_ret(&content) {
+            None
+        } else {
             let fork = content.fork();
             if let Ok(ty) = fork.parse::<Type>() {
                 if fork.peek(Token![=>]) {
@@ -29,14 +31,19 @@ impl TransCommand for ExprCommand {
                 None
             }
         };
-        let (expr, fallible): (Expr, _) = {
+        let (expr, fallible, returns_input): (Expr, _, _) = {
             if content.peek(Token![try]) {
                 content.parse::<Token![try]>()?;
                 let expr;
                 syn::parenthesized!(expr in content);
-                (expr.parse()?, true)
+                (expr.parse()?, true, false)
+            } else if peek_try_ret(&content) {
+                content.parse::<Ident>()?;
+                let expr;
+                syn::parenthesized!(expr in content);
+                (expr.parse()?, true, true)
             } else {
-                (content.parse()?, false)
+                (content.parse()?, false, false)
             }
         };
         let out_type = if content.peek(Token![=>]) {
@@ -56,6 +63,7 @@ impl TransCommand for ExprCommand {
             in_type,
             expr,
             fallible,
+            returns_input,
             message,
             out_type,
         }))
@@ -66,6 +74,7 @@ pub struct ExprHandler {
     in_type: Option<Type>,
     expr: Expr,
     fallible: bool,
+    returns_input: bool,
     message: Option<LitStr>,
     out_type: Option<Type>,
 }
@@ -83,9 +92,13 @@ impl TransHandler for ExprHandler {
         self.fallible
     }
 
-    fn message(&self, field: &Ident) -> Option<String> {
+    fn returns_input(&self) -> bool {
+        self.returns_input
+    }
+
+    fn message(&self, label: &str) -> Option<String> {
         Some(self.message.as_ref().map_or_else(
-            || format!("`{}`'s transform expression fails", field),
+            || format!("{}'s transform expression fails", label),
             |lit| lit.value(),
         ))
     }
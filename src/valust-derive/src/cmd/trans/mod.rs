@@ -1,12 +1,30 @@
 use proc_macro2::TokenStream;
 use syn::parse::ParseStream;
+use syn::token::Paren;
 use syn::{Ident, Type};
 
+mod captures;
 mod expr;
 mod func;
+mod sanitize;
+mod with;
 
-pub const TRANS_COMMANDS: &[&dyn TransCommand] =
-    &[&expr::ExprCommand, &func::FuncCommand];
+pub const TRANS_COMMANDS: &[&dyn TransCommand] = &[
+    &expr::ExprCommand,
+    &func::FuncCommand,
+    &with::WithCommand,
+    &captures::CapturesCommand,
+    &sanitize::TRIM,
+    &sanitize::TRIM_START,
+    &sanitize::TRIM_END,
+    &sanitize::LOWERCASE,
+    &sanitize::UPPERCASE,
+    &sanitize::COLLAPSE_WHITESPACE,
+    &sanitize::STRIP_CONTROL_CHARS,
+    &sanitize::NFC,
+    &sanitize::NFKC,
+    &sanitize::EMPTY_TO_NONE,
+];
 
 pub trait TransCommand {
     fn ident(&self) -> &'static str;
@@ -19,9 +37,45 @@ pub trait TransHandler {
 
     fn out_type(&self) -> Option<Type>;
 
+    /// Input type inferred from the output type, for transformers without
+    /// declared types. `None` means the input has the output's type.
+    fn in_type_for(&self, _out: &Type) -> Option<Type> {
+        None
+    }
+
     fn gen_transformer_expr(&self, field: &Ident) -> TokenStream;
 
-    fn message(&self, field: &Ident) -> Option<String>;
+    /// Display of the expression in errors, instead of its tokens.
+    fn gen_expr_display(&self) -> Option<String> {
+        None
+    }
+
+    fn message(&self, label: &str) -> Option<String>;
 
     fn is_fallible(&self) -> bool;
+
+    /// Whether the transformer hands its input back on failure,
+    /// i.e. it returns `Result<T, valust::error::transform::Rejected<I, E>>`.
+    fn returns_input(&self) -> bool {
+        false
+    }
+
+    /// Whether the expression is a `valust::transformer::Transformer` object
+    /// instead of the transformed value.
+    fn applies_transformer(&self) -> bool {
+        false
+    }
+
+    /// Whether the expression returns
+    /// `Result<T, valust::__private::CapturesFailure>`, reported with one
+    /// error per failing group.
+    fn parses_captures(&self) -> bool {
+        false
+    }
+}
+
+/// Check if the input starts with `try_ret(...)`.
+fn peek_try_ret(input: ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<Ident>().is_ok_and(|ident| ident == "try_ret") && fork.peek(Paren)
 }
@@ -0,0 +1,118 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::ParseStream;
+use syn::{Ident, Type};
+
+use super::{TransCommand, TransHandler};
+use crate::utils::types::option_inner;
+
+/// Built-in string transformers, applying the `valust::transformer` object of
+/// the same name.
+///
+/// They take no argument, and their types are inferred from the field.
+pub struct SanitizeCommand {
+    ident: &'static str,
+    transformer: &'static str,
+    /// Whether the transformer turns `T` into `Option<T>`.
+    wraps_option: bool,
+    /// Feature needed by the transformer, if it is not enabled.
+    missing_feature: Option<&'static str>,
+}
+
+const fn sanitizer(ident: &'static str, transformer: &'static str) -> SanitizeCommand {
+    SanitizeCommand {
+        ident,
+        transformer,
+        wraps_option: false,
+        missing_feature: None,
+    }
+}
+
+const MISSING_NORMALIZATION: Option<&str> = if cfg!(feature = "normalization") {
+    None
+} else {
+    Some("normalization")
+};
+
+pub const TRIM: SanitizeCommand = sanitizer("trim", "Trim");
+pub const TRIM_START: SanitizeCommand = sanitizer("trim_start", "TrimStart");
+pub const TRIM_END: SanitizeCommand = sanitizer("trim_end", "TrimEnd");
+pub const LOWERCASE: SanitizeCommand = sanitizer("lowercase", "Lowercase");
+pub const UPPERCASE: SanitizeCommand = sanitizer("uppercase", "Uppercase");
+pub const COLLAPSE_WHITESPACE: SanitizeCommand =
+    sanitizer("collapse_whitespace", "CollapseWhitespace");
+pub const STRIP_CONTROL_CHARS: SanitizeCommand =
+    sanitizer("strip_control_chars", "StripControlChars");
+pub const NFC: SanitizeCommand = SanitizeCommand {
+    missing_feature: MISSING_NORMALIZATION,
+    ..sanitizer("nfc", "Nfc")
+};
+pub const NFKC: SanitizeCommand = SanitizeCommand {
+    missing_feature: MISSING_NORMALIZATION,
+    ..sanitizer("nfkc", "Nfkc")
+};
+pub const EMPTY_TO_NONE: SanitizeCommand = SanitizeCommand {
+    wraps_option: true,
+    ..sanitizer("empty_to_none", "EmptyToNone")
+};
+
+impl TransCommand for SanitizeCommand {
+    fn ident(&self) -> &'static str {
+        self.ident
+    }
+
+    fn parse_inner(&self, tt: ParseStream) -> syn::Result<Box<dyn TransHandler>> {
+        if let Some(feature) = self.missing_feature {
+            return Err(syn::Error::new(
+                tt.span(),
+                format!("feature `{}` is not enabled", feature),
+            ));
+        }
+
+        Ok(Box::new(SanitizeHandler {
+            transformer: format_ident!("{}", self.transformer, span = tt.span()),
+            wraps_option: self.wraps_option,
+        }))
+    }
+}
+
+struct SanitizeHandler {
+    transformer: Ident,
+    wraps_option: bool,
+}
+
+impl TransHandler for SanitizeHandler {
+    fn in_type(&self) -> Option<Type> {
+        None
+    }
+
+    fn out_type(&self) -> Option<Type> {
+        None
+    }
+
+    fn in_type_for(&self, out: &Type) -> Option<Type> {
+        if self.wraps_option {
+            option_inner(out)
+        } else {
+            None
+        }
+    }
+
+    fn gen_transformer_expr(&self, field: &Ident) -> TokenStream {
+        let transformer = &self.transformer;
+        quote! {
+            ::valust::__private::infallible(::valust::transformer::Transformer::transform(
+                &::valust::transformer::#transformer,
+                #field,
+            ))
+        }
+    }
+
+    fn message(&self, _label: &str) -> Option<String> {
+        None
+    }
+
+    fn is_fallible(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,86 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{Expr, Ident, LitStr, Token, Type};
+
+use super::{TransCommand, TransHandler};
+
+/// `with([In =>] <transformer> [=> Out])`, applying a
+/// `valust::transformer::Transformer`.
+pub struct WithCommand;
+
+impl TransCommand for WithCommand {
+    fn ident(&self) -> &'static str {
+        "with"
+    }
+
+    fn parse_inner(&self, tt: ParseStream) -> syn::Result<Box<dyn TransHandler>> {
+        let content;
+        syn::parenthesized!(content in tt);
+        let in_type = {
+            let fork = content.fork();
+            if fork.parse::<Type>().is_ok() && fork.peek(Token![=>]) {
+                let ty = content.parse::<Type>()?;
+                content.parse::<Token![=>]>()?;
+                Some(ty)
+            } else {
+                None
+            }
+        };
+        let transformer = content.parse()?;
+        let out_type = if content.peek(Token![=>]) {
+            content.parse::<Token![=>]>()?;
+            Some(content.parse()?)
+        } else {
+            None
+        };
+        let message = if content.peek(Token![,]) {
+            content.parse::<Token![,]>()?;
+            Some(content.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Box::new(WithHandler {
+            in_type,
+            transformer,
+            out_type,
+            message,
+        }))
+    }
+}
+
+struct WithHandler {
+    in_type: Option<Type>,
+    transformer: Expr,
+    out_type: Option<Type>,
+    message: Option<LitStr>,
+}
+
+impl TransHandler for WithHandler {
+    fn in_type(&self) -> Option<Type> {
+        self.in_type.clone()
+    }
+
+    fn out_type(&self) -> Option<Type> {
+        self.out_type.clone()
+    }
+
+    fn gen_transformer_expr(&self, _field: &Ident) -> TokenStream {
+        let transformer = &self.transformer;
+        quote! { #transformer }
+    }
+
+    /// Without a message, the error of the transformer is shown.
+    fn message(&self, _label: &str) -> Option<String> {
+        self.message.as_ref().map(LitStr::value)
+    }
+
+    fn is_fallible(&self) -> bool {
+        true
+    }
+
+    fn applies_transformer(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,107 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+use syn::parse::ParseStream;
+
+use super::{ValidCommand, ValidHandler};
+
+/// Validators checking the characters of a string-like value.
+pub struct CharClassCommand {
+    ident: &'static str,
+    /// Check of the `&str` stored in `valust_str`.
+    check: fn() -> TokenStream,
+    /// Message, where `{}` is the label.
+    message: &'static str,
+    /// Feature required by the check, and whether it is enabled.
+    feature: Option<(&'static str, bool)>,
+}
+
+pub const ASCII: CharClassCommand = CharClassCommand {
+    ident: "ascii",
+    check: || quote! { valust_str.is_ascii() },
+    message: "must only contain ASCII characters",
+    feature: None,
+};
+
+pub const ALPHANUMERIC: CharClassCommand = CharClassCommand {
+    ident: "alphanumeric",
+    check: || quote! { valust_str.chars().all(char::is_alphanumeric) },
+    message: "must only contain letters and digits",
+    feature: None,
+};
+
+pub const PRINTABLE: CharClassCommand = CharClassCommand {
+    ident: "printable",
+    check: || quote! { ::valust::__private::is_printable(valust_str) },
+    message: "must only contain printable characters",
+    feature: Some(("general-category", cfg!(feature = "general-category"))),
+};
+
+pub const NO_CONTROL_CHARS: CharClassCommand = CharClassCommand {
+    ident: "no_control_chars",
+    check: || {
+        quote! {
+            !valust_str
+                .chars()
+                .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+        }
+    },
+    message: "must not contain control characters",
+    feature: None,
+};
+
+pub const NO_WHITESPACE_EDGES: CharClassCommand = CharClassCommand {
+    ident: "no_whitespace_edges",
+    check: || quote! { valust_str.trim().len() == valust_str.len() },
+    message: "must not start or end with whitespace",
+    feature: None,
+};
+
+impl ValidCommand for CharClassCommand {
+    fn ident(&self) -> &'static str {
+        self.ident
+    }
+
+    fn parse_inner(&self, tt: ParseStream) -> syn::Result<Box<dyn ValidHandler>> {
+        if let Some((feature, false)) = self.feature {
+            return Err(syn::Error::new(
+                tt.span(),
+                format!("feature `{}` is not enabled", feature),
+            ));
+        }
+
+        Ok(Box::new(CharClassHandler {
+            ident: self.ident,
+            check: (self.check)(),
+            message: self.message,
+        }))
+    }
+}
+
+struct CharClassHandler {
+    ident: &'static str,
+    check: TokenStream,
+    message: &'static str,
+}
+
+impl ValidHandler for CharClassHandler {
+    fn gen_validator_expr(&self, field: &Ident) -> TokenStream {
+        let check = &self.check;
+        quote! {{
+            let valust_str: &str = ::core::convert::AsRef::<str>::as_ref(&#field);
+            #check
+        }}
+    }
+
+    fn gen_expr_display(&self, _field: &Ident) -> Option<String> {
+        Some(self.ident.to_owned())
+    }
+
+    fn message(&self, label: &str) -> Option<String> {
+        Some(format!("{} {}", label, self.message))
+    }
+
+    fn is_fallible(&self) -> bool {
+        false
+    }
+}
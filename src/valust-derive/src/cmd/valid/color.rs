@@ -2,10 +2,12 @@ use proc_macro2::{Span, TokenStream};
 use syn::parse::ParseStream;
 use syn::punctuated::Punctuated;
 use syn::token::Paren;
-use syn::{Expr, Ident, LitStr, Token};
+use syn::{Ident, LitStr, Token};
 
 use super::regex::gen_regex_expr;
 use super::{ValidCommand, ValidHandler};
+use crate::utils::check_regex::check_regex;
+use crate::utils::parse_kv::parse_kv;
 use crate::utils::require_bool::require_bool_expr;
 use crate::utils::require_lit_str::require_lit_str;
 use crate::utils::require_single::require_single_fallible;
@@ -28,12 +30,13 @@ impl ValidCommand for ColorCommand {
             let lit: LitStr = tt.parse()?;
             let span = lit.span();
             let ty = ColorType::from_text(lit)?;
-            Ok(Box::new(ColorHandler {
+            ColorHandler {
                 span,
                 color_type: ty,
                 prefix: Default::default(),
                 compat: false,
-            }))
+            }
+            .checked()
         }
         // color(xxx)
         // type, prefix, compat
@@ -45,16 +48,6 @@ impl ValidCommand for ColorCommand {
             let content;
             syn::parenthesized!(content in tt);
 
-            fn parse_kv(buf: ParseStream) -> syn::Result<(Ident, Option<Expr>)> {
-                let ident: Ident = buf.parse()?;
-                if buf.peek(Token![=]) {
-                    buf.parse::<Token![=]>()?;
-                    let expr: Expr = buf.parse()?;
-                    return Ok((ident, Some(expr)));
-                }
-                Ok((ident, None))
-            }
-
             for (ident, expr) in
                 Punctuated::<_, Token![,]>::parse_terminated_with(&content, parse_kv)?
             {
@@ -89,19 +82,21 @@ impl ValidCommand for ColorCommand {
                 }
             }
 
-            Ok(Box::new(ColorHandler {
+            ColorHandler {
                 span: base_span,
                 color_type: color_type.unwrap_or_default(),
                 prefix: prefix.unwrap_or_default(),
                 compat: compat.unwrap_or(true),
-            }))
+            }
+            .checked()
         } else {
-            Ok(Box::new(ColorHandler {
+            ColorHandler {
                 span: tt.span(),
                 color_type: Default::default(),
                 prefix: Default::default(),
                 compat: true,
-            }))
+            }
+            .checked()
         }
     }
 }
@@ -114,6 +109,12 @@ struct ColorHandler {
 }
 
 impl ColorHandler {
+    /// Check the generated regex during macro expansion.
+    fn checked(self) -> syn::Result<Box<dyn ValidHandler>> {
+        check_regex(&self.to_regex(), self.span)?;
+        Ok(Box::new(self))
+    }
+
     pub fn to_regex(&self) -> String {
         self.color_type.to_regex(self.compat, self.prefix)
     }
@@ -124,12 +125,12 @@ impl ValidHandler for ColorHandler {
         Some(self.to_regex())
     }
 
-    fn message(&self, field: &Ident) -> Option<String> {
-        Some(format!("`{}` is not a valid color literal", field))
+    fn message(&self, label: &str) -> Option<String> {
+        Some(format!("{} is not a valid color literal", label))
     }
 
     fn is_fallible(&self) -> bool {
-        false
+        true
     }
 
     fn gen_validator_expr(&self, field: &Ident) -> TokenStream {
@@ -0,0 +1,81 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::parse::ParseStream;
+use syn::{Expr, Ident};
+
+use super::{ValidCommand, ValidHandler};
+
+/// `contains(<pattern>)`, `starts_with(<pattern>)` and `ends_with(<pattern>)`.
+///
+/// These call the method of the same name on the field, so that they work with
+/// both strings and slices.
+pub struct PatternCommand {
+    method: &'static str,
+    verb: &'static str,
+}
+
+pub const CONTAINS: PatternCommand = PatternCommand {
+    method: "contains",
+    verb: "contain",
+};
+
+pub const STARTS_WITH: PatternCommand = PatternCommand {
+    method: "starts_with",
+    verb: "start with",
+};
+
+pub const ENDS_WITH: PatternCommand = PatternCommand {
+    method: "ends_with",
+    verb: "end with",
+};
+
+impl ValidCommand for PatternCommand {
+    fn ident(&self) -> &'static str {
+        self.method
+    }
+
+    fn parse_inner(&self, tt: ParseStream) -> syn::Result<Box<dyn ValidHandler>> {
+        let content;
+        syn::parenthesized!(content in tt);
+        Ok(Box::new(PatternHandler {
+            method: self.method,
+            verb: self.verb,
+            pattern: content.parse()?,
+        }))
+    }
+}
+
+struct PatternHandler {
+    method: &'static str,
+    verb: &'static str,
+    pattern: Expr,
+}
+
+impl ValidHandler for PatternHandler {
+    fn gen_validator_expr(&self, field: &Ident) -> TokenStream {
+        let method = Ident::new(self.method, field.span());
+        let pattern = &self.pattern;
+        quote! { #field.#method(#pattern) }
+    }
+
+    fn gen_expr_display(&self, _field: &Ident) -> Option<String> {
+        Some(format!(
+            "{}({})",
+            self.method,
+            self.pattern.to_token_stream()
+        ))
+    }
+
+    fn message(&self, label: &str) -> Option<String> {
+        Some(format!("{} must {} {{pattern}}", label, self.verb))
+    }
+
+    fn params(&self) -> Vec<(&'static str, TokenStream)> {
+        let pattern = &self.pattern;
+        vec![("pattern", quote! { #pattern })]
+    }
+
+    fn is_fallible(&self) -> bool {
+        false
+    }
+}
@@ -54,9 +54,9 @@ impl ValidHandler for FuncHandler {
         self.fallible
     }
 
-    fn message(&self, field: &Ident) -> Option<String> {
+    fn message(&self, label: &str) -> Option<String> {
         Some(self.message.as_ref().map_or_else(
-            || format!("`{}`'s validator function evaluate to `false`", field),
+            || format!("{}'s validator function evaluate to `false`", label),
             |lit| lit.value(),
         ))
     }
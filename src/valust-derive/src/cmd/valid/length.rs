@@ -0,0 +1,226 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::parse::ParseStream;
+use syn::{Expr, Ident};
+
+use super::{ValidCommand, ValidHandler};
+use crate::utils::parse_kv::parse_kv_list;
+use crate::utils::require_single::require_single_fallible;
+
+const LEN_SYNTAX_ERR: &str = "\
+    Invalid length validator usage.\n\
+    Expect `<cmd>(min = <expr>, max = <expr>)` or `<cmd>(eq = <expr>)`, \
+    where `min` and `max` are optional.";
+
+/// What a length counts.
+#[derive(Clone, Copy)]
+enum LenUnit {
+    /// `len()`, i.e. bytes of strings and items of collections.
+    Len,
+    /// Unicode scalar values.
+    Chars,
+    /// Extended grapheme clusters, i.e. user-perceived characters.
+    Graphemes,
+}
+
+pub struct LenCommand {
+    ident: &'static str,
+    unit: LenUnit,
+}
+
+pub const LEN: LenCommand = LenCommand {
+    ident: "len",
+    unit: LenUnit::Len,
+};
+
+pub const CHAR_LEN: LenCommand = LenCommand {
+    ident: "char_len",
+    unit: LenUnit::Chars,
+};
+
+pub const GRAPHEME_LEN: LenCommand = LenCommand {
+    ident: "grapheme_len",
+    unit: LenUnit::Graphemes,
+};
+
+impl ValidCommand for LenCommand {
+    fn ident(&self) -> &'static str {
+        self.ident
+    }
+
+    fn parse_inner(&self, tt: ParseStream) -> syn::Result<Box<dyn ValidHandler>> {
+        #[cfg(not(feature = "grapheme"))]
+        if matches!(self.unit, LenUnit::Graphemes) {
+            return Err(syn::Error::new(
+                tt.span(),
+                "feature `grapheme` is not enabled",
+            ));
+        }
+
+        let span = tt.span();
+        Ok(Box::new(LenHandler::parse(
+            tt, span, self.ident, self.unit,
+        )?))
+    }
+}
+
+pub struct NonEmptyCommand;
+
+impl ValidCommand for NonEmptyCommand {
+    fn ident(&self) -> &'static str {
+        "non_empty"
+    }
+
+    fn parse_inner(&self, _tt: ParseStream) -> syn::Result<Box<dyn ValidHandler>> {
+        Ok(Box::new(NonEmptyHandler))
+    }
+}
+
+/// Bounds of a length.
+struct LenHandler {
+    ident: &'static str,
+    unit: LenUnit,
+    min: Option<Expr>,
+    max: Option<Expr>,
+    eq: Option<Expr>,
+}
+
+impl LenHandler {
+    fn parse(
+        tt: ParseStream,
+        span: Span,
+        ident: &'static str,
+        unit: LenUnit,
+    ) -> syn::Result<Self> {
+        let mut min = None;
+        let mut max = None;
+        let mut eq = None;
+        for (key, expr) in parse_kv_list(tt)? {
+            let slot = match key.to_string().as_str() {
+                "min" => &mut min,
+                "max" => &mut max,
+                "eq" => &mut eq,
+                _ => return Err(syn::Error::new(key.span(), LEN_SYNTAX_ERR)),
+            };
+            let expr =
+                expr.ok_or_else(|| syn::Error::new(key.span(), LEN_SYNTAX_ERR))?;
+            require_single_fallible(
+                Some(Ok(expr)),
+                slot,
+                &key.to_string(),
+                key.span(),
+            )?;
+        }
+        let valid = if eq.is_some() {
+            min.is_none() && max.is_none()
+        } else {
+            min.is_some() || max.is_some()
+        };
+        if !valid {
+            return Err(syn::Error::new(span, LEN_SYNTAX_ERR));
+        }
+        Ok(Self {
+            ident,
+            unit,
+            min,
+            max,
+            eq,
+        })
+    }
+
+    fn bounds(&self) -> impl Iterator<Item = (&'static str, &Expr)> {
+        [("min", &self.min), ("max", &self.max), ("eq", &self.eq)]
+            .into_iter()
+            .filter_map(|(name, expr)| expr.as_ref().map(|expr| (name, expr)))
+    }
+}
+
+impl ValidHandler for LenHandler {
+    fn gen_validator_expr(&self, field: &Ident) -> TokenStream {
+        let len = match self.unit {
+            LenUnit::Len => quote! { #field.len() },
+            LenUnit::Chars => quote! {
+                ::core::convert::AsRef::<str>::as_ref(&#field).chars().count()
+            },
+            LenUnit::Graphemes => quote! {
+                ::valust::__private::grapheme_len(::core::convert::AsRef::<str>::as_ref(&#field))
+            },
+        };
+        let check = match (&self.min, &self.max, &self.eq) {
+            (_, _, Some(eq)) => quote! { valust_len == (#eq) },
+            (Some(min), Some(max), _) => {
+                quote! { ((#min)..=(#max)).contains(&valust_len) }
+            }
+            (Some(min), None, _) => quote! { valust_len >= (#min) },
+            (None, Some(max), _) => quote! { valust_len <= (#max) },
+            (None, None, None) => unreachable!("length validator without bounds"),
+        };
+        quote! {{
+            let valust_len = #len;
+            #check
+        }}
+    }
+
+    fn gen_expr_display(&self, _field: &Ident) -> Option<String> {
+        let bounds = self
+            .bounds()
+            .map(|(name, expr)| format!("{} = {}", name, expr.to_token_stream()))
+            .collect::<Vec<_>>();
+        Some(format!("{}({})", self.ident, bounds.join(", ")))
+    }
+
+    fn message(&self, label: &str) -> Option<String> {
+        let (min, max, eq) =
+            (self.min.is_some(), self.max.is_some(), self.eq.is_some());
+        Some(match self.unit {
+            LenUnit::Len => {
+                let bound = match (min, max) {
+                    _ if eq => "of {eq}",
+                    (true, true) => "between {min} and {max}",
+                    (true, false) => "of at least {min}",
+                    _ => "of at most {max}",
+                };
+                format!("{} must have a length {}", label, bound)
+            }
+            LenUnit::Chars | LenUnit::Graphemes => {
+                let bound = match (min, max) {
+                    _ if eq => "exactly {eq}",
+                    (true, true) => "between {min} and {max}",
+                    (true, false) => "at least {min}",
+                    _ => "at most {max}",
+                };
+                format!("{} must be {} characters long", label, bound)
+            }
+        })
+    }
+
+    fn params(&self) -> Vec<(&'static str, TokenStream)> {
+        self.bounds()
+            .map(|(name, expr)| (name, quote! { #expr }))
+            .collect()
+    }
+
+    fn is_fallible(&self) -> bool {
+        false
+    }
+}
+
+struct NonEmptyHandler;
+
+impl ValidHandler for NonEmptyHandler {
+    fn gen_validator_expr(&self, field: &Ident) -> TokenStream {
+        quote! { !#field.is_empty() }
+    }
+
+    fn gen_expr_display(&self, _field: &Ident) -> Option<String> {
+        Some("non_empty".to_owned())
+    }
+
+    fn message(&self, label: &str) -> Option<String> {
+        Some(format!("{} must not be empty", label))
+    }
+
+    fn is_fallible(&self) -> bool {
+        false
+    }
+}
@@ -2,12 +2,18 @@ use proc_macro2::TokenStream;
 use syn::Ident;
 use syn::parse::ParseStream;
 
+mod charset;
 mod color;
+mod contains;
 mod expr;
 mod func;
+mod length;
+mod one_of;
+mod range;
 mod regex;
 #[cfg(feature = "regex-utils")]
 mod regex_alias;
+mod with;
 
 pub const VALID_COMMANDS: &[&dyn ValidCommand] = &[
     &regex::RegexCommand,
@@ -20,6 +26,23 @@ pub const VALID_COMMANDS: &[&dyn ValidCommand] = &[
     #[cfg(feature = "regex-utils")]
     &regex_alias::UsernameCommand,
     &color::ColorCommand,
+    &length::LEN,
+    &length::CHAR_LEN,
+    &length::GRAPHEME_LEN,
+    &length::NonEmptyCommand,
+    &range::RangeCommand,
+    &contains::CONTAINS,
+    &contains::STARTS_WITH,
+    &contains::ENDS_WITH,
+    &charset::ASCII,
+    &charset::ALPHANUMERIC,
+    &charset::PRINTABLE,
+    &charset::NO_CONTROL_CHARS,
+    &charset::NO_WHITESPACE_EDGES,
+    &one_of::ONE_OF,
+    &one_of::NOT_ONE_OF,
+    &one_of::MatchesCommand,
+    &with::WithCommand,
 ];
 
 pub trait ValidCommand {
@@ -43,7 +66,22 @@ pub trait ValidHandler {
     /// Error message.
     ///
     /// This will be used like `Some(self.message())`.
-    fn message(&self, field: &Ident) -> Option<String>;
+    fn message(&self, label: &str) -> Option<String>;
+
+    /// Parameters stored in `ValidateError::params`, as `Debug` expressions.
+    ///
+    /// Messages may refer to them with `{<name>}` placeholders.
+    fn params(&self) -> Vec<(&'static str, TokenStream)> {
+        Vec::new()
+    }
 
     fn is_fallible(&self) -> bool;
+
+    /// Whether the expression returns a `Result<(), valust::validator::Violation>`
+    /// instead of a boolean.
+    ///
+    /// The message and parameters of the error then come from the violation.
+    fn reports_violation(&self) -> bool {
+        false
+    }
 }
@@ -0,0 +1,142 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, Pat, Token};
+
+use super::{ValidCommand, ValidHandler};
+
+const ONE_OF_EMPTY_ERR: &str = "expect at least one value";
+
+/// `one_of(<expr>, ...)` and `not_one_of(<expr>, ...)`.
+pub struct OneOfCommand {
+    ident: &'static str,
+    negated: bool,
+}
+
+pub const ONE_OF: OneOfCommand = OneOfCommand {
+    ident: "one_of",
+    negated: false,
+};
+
+pub const NOT_ONE_OF: OneOfCommand = OneOfCommand {
+    ident: "not_one_of",
+    negated: true,
+};
+
+impl ValidCommand for OneOfCommand {
+    fn ident(&self) -> &'static str {
+        self.ident
+    }
+
+    fn parse_inner(&self, tt: ParseStream) -> syn::Result<Box<dyn ValidHandler>> {
+        let content;
+        syn::parenthesized!(content in tt);
+        let values = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+        if values.is_empty() {
+            return Err(syn::Error::new(content.span(), ONE_OF_EMPTY_ERR));
+        }
+        Ok(Box::new(OneOfHandler {
+            ident: self.ident,
+            negated: self.negated,
+            values: values.into_iter().collect(),
+        }))
+    }
+}
+
+struct OneOfHandler {
+    ident: &'static str,
+    negated: bool,
+    values: Vec<Expr>,
+}
+
+impl ValidHandler for OneOfHandler {
+    fn gen_validator_expr(&self, field: &Ident) -> TokenStream {
+        let values = &self.values;
+        let found = quote! { #(#field == (#values))||* };
+        if self.negated {
+            quote! { !(#found) }
+        } else {
+            found
+        }
+    }
+
+    fn gen_expr_display(&self, _field: &Ident) -> Option<String> {
+        let values = self
+            .values
+            .iter()
+            .map(|v| v.to_token_stream().to_string())
+            .collect::<Vec<_>>();
+        Some(format!("{}({})", self.ident, values.join(", ")))
+    }
+
+    fn message(&self, label: &str) -> Option<String> {
+        Some(if self.negated {
+            format!("{} must not be one of {{forbidden}}", label)
+        } else {
+            format!("{} must be one of {{allowed}}", label)
+        })
+    }
+
+    fn params(&self) -> Vec<(&'static str, TokenStream)> {
+        let values = &self.values;
+        let name = if self.negated { "forbidden" } else { "allowed" };
+        vec![(name, quote! { [#(#values),*] })]
+    }
+
+    fn is_fallible(&self) -> bool {
+        false
+    }
+}
+
+/// `matches(<pattern>)`.
+pub struct MatchesCommand;
+
+impl ValidCommand for MatchesCommand {
+    fn ident(&self) -> &'static str {
+        "matches"
+    }
+
+    fn parse_inner(&self, tt: ParseStream) -> syn::Result<Box<dyn ValidHandler>> {
+        let content;
+        syn::parenthesized!(content in tt);
+        Ok(Box::new(MatchesHandler {
+            pattern: Pat::parse_multi_with_leading_vert(&content)?,
+        }))
+    }
+}
+
+struct MatchesHandler {
+    pattern: Pat,
+}
+
+impl MatchesHandler {
+    fn pattern_text(&self) -> String {
+        self.pattern.to_token_stream().to_string()
+    }
+}
+
+impl ValidHandler for MatchesHandler {
+    fn gen_validator_expr(&self, field: &Ident) -> TokenStream {
+        let pattern = &self.pattern;
+        quote! { ::core::matches!(#field, #pattern) }
+    }
+
+    fn gen_expr_display(&self, _field: &Ident) -> Option<String> {
+        Some(format!("matches({})", self.pattern_text()))
+    }
+
+    fn message(&self, label: &str) -> Option<String> {
+        Some(format!("{} must match `{{pattern}}`", label))
+    }
+
+    fn params(&self) -> Vec<(&'static str, TokenStream)> {
+        // the pattern is not a value, so it is shown as is
+        let pattern = self.pattern_text();
+        vec![("pattern", quote! { ::core::format_args!("{}", #pattern) })]
+    }
+
+    fn is_fallible(&self) -> bool {
+        false
+    }
+}
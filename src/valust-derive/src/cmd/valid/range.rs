@@ -0,0 +1,113 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::parse::ParseStream;
+use syn::{Expr, Ident};
+
+use super::{ValidCommand, ValidHandler};
+use crate::utils::parse_kv::parse_kv_list;
+use crate::utils::require_single::require_single_fallible;
+
+const RANGE_SYNTAX_ERR: &str = "\
+    Invalid `range` usage.\n\
+    Expect `range(min = <expr>, max = <expr>, exclusive)`, where `min` or \
+    `max` may be omitted, and `exclusive` excludes `max`.";
+
+pub struct RangeCommand;
+
+impl ValidCommand for RangeCommand {
+    fn ident(&self) -> &'static str {
+        "range"
+    }
+
+    fn parse_inner(&self, tt: ParseStream) -> syn::Result<Box<dyn ValidHandler>> {
+        let span = tt.span();
+        let mut min = None;
+        let mut max = None;
+        let mut exclusive = false;
+        for (ident, expr) in parse_kv_list(tt)? {
+            match (ident.to_string().as_str(), expr) {
+                ("min", Some(expr)) => require_single_fallible(
+                    Some(Ok(expr)),
+                    &mut min,
+                    "min",
+                    ident.span(),
+                )?,
+                ("max", Some(expr)) => require_single_fallible(
+                    Some(Ok(expr)),
+                    &mut max,
+                    "max",
+                    ident.span(),
+                )?,
+                ("exclusive", None) => exclusive = true,
+                _ => return Err(syn::Error::new(ident.span(), RANGE_SYNTAX_ERR)),
+            }
+        }
+        if min.is_none() && max.is_none() || exclusive && max.is_none() {
+            return Err(syn::Error::new(span, RANGE_SYNTAX_ERR));
+        }
+        Ok(Box::new(RangeHandler {
+            min,
+            max,
+            exclusive,
+        }))
+    }
+}
+
+struct RangeHandler {
+    min: Option<Expr>,
+    max: Option<Expr>,
+    exclusive: bool,
+}
+
+impl ValidHandler for RangeHandler {
+    fn gen_validator_expr(&self, field: &Ident) -> TokenStream {
+        match (&self.min, &self.max, self.exclusive) {
+            (Some(min), Some(max), false) => {
+                quote! { ((#min)..=(#max)).contains(&#field) }
+            }
+            (Some(min), Some(max), true) => {
+                quote! { ((#min)..(#max)).contains(&#field) }
+            }
+            (Some(min), None, _) => quote! { #field >= (#min) },
+            (None, Some(max), false) => quote! { #field <= (#max) },
+            (None, Some(max), true) => quote! { #field < (#max) },
+            (None, None, _) => unreachable!("`range` without bounds"),
+        }
+    }
+
+    fn gen_expr_display(&self, _field: &Ident) -> Option<String> {
+        let min = self.min.as_ref().map(|e| e.to_token_stream().to_string());
+        let max = self.max.as_ref().map(|e| e.to_token_stream().to_string());
+        let op = if self.exclusive { ".." } else { "..=" };
+        Some(format!(
+            "range({}{}{})",
+            min.unwrap_or_default(),
+            if max.is_some() { op } else { ".." },
+            max.unwrap_or_default()
+        ))
+    }
+
+    fn message(&self, label: &str) -> Option<String> {
+        let bound = match (&self.min, &self.max, self.exclusive) {
+            (Some(_), Some(_), false) => "between {min} and {max}",
+            (Some(_), Some(_), true) => "at least {min} and less than {max}",
+            (Some(_), None, _) => "at least {min}",
+            (None, _, false) => "at most {max}",
+            (None, _, true) => "less than {max}",
+        };
+        Some(format!("{} must be {}", label, bound))
+    }
+
+    fn params(&self) -> Vec<(&'static str, TokenStream)> {
+        [("min", &self.min), ("max", &self.max)]
+            .into_iter()
+            .filter_map(|(name, expr)| {
+                expr.as_ref().map(|expr| (name, quote! { #expr }))
+            })
+            .collect()
+    }
+
+    fn is_fallible(&self) -> bool {
+        false
+    }
+}
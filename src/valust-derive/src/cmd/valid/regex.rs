@@ -5,6 +5,7 @@ use syn::spanned::Spanned;
 use syn::{Expr, Ident, LitStr, Token};
 
 use super::{ValidCommand, ValidHandler};
+use crate::utils::check_regex::check_regex_lit;
 
 pub struct RegexCommand;
 
@@ -17,17 +18,15 @@ impl ValidCommand for RegexCommand {
         let content;
         syn::parenthesized!(content in tt);
         let regex = content.parse::<Expr>()?;
+        check_regex_lit(&regex)?;
+        let mut message = None;
         if content.peek(Token![,]) {
             content.parse::<Token![,]>()?;
             if !content.is_empty() {
-                let message = Some(content.parse()?);
-                return Ok(Box::new(RegexHandler { regex, message }));
+                message = Some(content.parse()?);
             }
         }
-        Ok(Box::new(RegexHandler {
-            regex,
-            message: None,
-        }))
+        Ok(Box::new(RegexHandler { regex, message }))
     }
 }
 
@@ -41,9 +40,9 @@ impl ValidHandler for RegexHandler {
         gen_regex_expr(field, &self.regex, self.regex.span())
     }
 
-    fn message(&self, field: &Ident) -> Option<String> {
+    fn message(&self, label: &str) -> Option<String> {
         Some(self.message.as_ref().map_or_else(
-            || format!("`{}` does not match the regex", field),
+            || format!("{} does not match the regex", label),
             |lit| lit.value(),
         ))
     }
@@ -53,10 +52,14 @@ impl ValidHandler for RegexHandler {
     }
 
     fn is_fallible(&self) -> bool {
-        false
+        true
     }
 }
 
+/// Match a regex compiled on first use.
+///
+/// Fails with the error of the regex engine if the regex is invalid, which
+/// literals are checked against at compile time, or if matching fails.
 pub(super) fn gen_regex_expr(
     field: &Ident,
     regex: impl ToTokens,
@@ -65,9 +68,8 @@ pub(super) fn gen_regex_expr(
     let regex_name = format_ident!("valust_valid_regex_{}", field, span = span);
 
     quote! {{
-        static #regex_name: ::std::sync::LazyLock<::valust::regex::Regex> = ::std::sync::LazyLock::new(|| {
-            ::valust::regex::Regex::new(#regex).unwrap()
-        });
+        static #regex_name: ::valust::__private::CachedRegex =
+            ::valust::__private::CachedRegex::new(|| ::valust::__private::Regex::new(#regex));
         #regex_name.is_match(&#field)
     }}
 }
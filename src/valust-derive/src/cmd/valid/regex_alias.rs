@@ -28,11 +28,11 @@ macro_rules! __valust_regex_alias {
 
             impl ValidHandler for [< $ident:camel Handler >] {
                 fn is_fallible(&self) -> bool {
-                    false
+                    true
                 }
 
-                fn message(&self, field: &Ident) -> Option<String> {
-                    Some(format!($fmt, field))
+                fn message(&self, label: &str) -> Option<String> {
+                    Some(format!($fmt, label))
                 }
 
                 fn gen_expr_display(&self, _field: &Ident) -> Option<String> {
@@ -50,20 +50,20 @@ macro_rules! __valust_regex_alias {
 __valust_regex_alias!(
     email
         feature("email")
-        fmt("`{}` is not a valid email address")
+        fmt("{} is not a valid email address")
     = valust_regex_utils::EMAIL
 );
 
 __valust_regex_alias!(
     url
         feature("url")
-        fmt("`{}` is not a valid URL")
+        fmt("{} is not a valid URL")
     = valust_regex_utils::URL
 );
 
 __valust_regex_alias!(
     username
         feature("username")
-        fmt("`{}` is not a valid username")
+        fmt("{} is not a valid username")
     = valust_regex_utils::USERNAME
 );
@@ -0,0 +1,51 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::parse::ParseStream;
+use syn::{Expr, Ident};
+
+use super::{ValidCommand, ValidHandler};
+
+/// `with(<validator>)`, applying a `valust::validator::Validator`.
+pub struct WithCommand;
+
+impl ValidCommand for WithCommand {
+    fn ident(&self) -> &'static str {
+        "with"
+    }
+
+    fn parse_inner(&self, tt: ParseStream) -> syn::Result<Box<dyn ValidHandler>> {
+        let content;
+        syn::parenthesized!(content in tt);
+        Ok(Box::new(WithHandler {
+            validator: content.parse()?,
+        }))
+    }
+}
+
+struct WithHandler {
+    validator: Expr,
+}
+
+impl ValidHandler for WithHandler {
+    fn gen_validator_expr(&self, field: &Ident) -> TokenStream {
+        let validator = &self.validator;
+        quote! { ::valust::validator::Validator::check(&(#validator), &#field) }
+    }
+
+    fn gen_expr_display(&self, _field: &Ident) -> Option<String> {
+        Some(format!("with({})", self.validator.to_token_stream()))
+    }
+
+    /// The message comes from the violation.
+    fn message(&self, _label: &str) -> Option<String> {
+        None
+    }
+
+    fn is_fallible(&self) -> bool {
+        false
+    }
+
+    fn reports_violation(&self) -> bool {
+        true
+    }
+}
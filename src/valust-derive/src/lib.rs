@@ -9,6 +9,8 @@ mod utils;
 
 use proc_macro::TokenStream;
 use syn::{DeriveInput, parse_macro_input};
+use syntax::enumeration::Enumeration;
+use syntax::from_captures::Captures;
 use syntax::structure::Structure;
 
 // const FIELD_ATTRS: &[&str] = &["valid", "trans", "forward", "display"];
@@ -19,7 +21,18 @@ use syntax::structure::Structure;
 /// For full documentation, see the crates's README file.
 #[proc_macro_derive(
     Valust,
-    attributes(valid, trans, forward, forward_attr, pre, post, rename, forward_derive)
+    attributes(
+        valid,
+        trans,
+        forward,
+        forward_attr,
+        pre,
+        pre_trans,
+        post,
+        rename,
+        forward_derive,
+        valust
+    )
 )]
 pub fn valust_derive(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
@@ -35,3 +48,32 @@ pub fn valust_derive(item: TokenStream) -> TokenStream {
 
     expanded.into()
 }
+
+/// Derive `FromStr` for an enum with unit variants.
+///
+/// The error type is `valust::error::enums::ParseEnumError`, which lists the
+/// accepted names. For full documentation, see the crates's README file.
+#[proc_macro_derive(ValustEnum, attributes(valust))]
+pub fn valust_enum_derive(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+
+    match Enumeration::from_input(input) {
+        Ok(t) => t.gen_from_str_impl().into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derive `valust::captures::FromCaptures` for a struct with named fields.
+///
+/// Each field is parsed with `FromStr` from the regex group of the same name,
+/// and `Option` fields from optional groups. For full documentation, see the
+/// crates's README file.
+#[proc_macro_derive(FromCaptures)]
+pub fn from_captures_derive(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+
+    match Captures::from_input(input) {
+        Ok(t) => t.gen_from_captures_impl().into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,147 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{Ident, LitInt, Path};
+
+const VALUE_CAPTURE_ERR: &str = "\
+    Invalid `value` usage.\n\
+    Expect `debug`, `display`, `none`, `with = <path>`, \
+    `max_chars = <int>`, `max_bytes = <int>` or `max_items = <int>`.";
+
+/// How a field's value is formatted into an error report.
+#[derive(Clone, Default)]
+pub enum CaptureStyle {
+    /// Format the value with `Debug`.
+    #[default]
+    Debug,
+    /// Format the value with `Display`.
+    Display,
+    /// Do not capture the value at all.
+    Ignore,
+    /// Format the value with a user-provided function.
+    With(Path),
+}
+
+/// How a field's value is captured into an error report.
+///
+/// Unset items are inherited from the structure-level options.
+#[derive(Clone, Default)]
+pub struct ValueCapture {
+    pub style: Option<CaptureStyle>,
+    pub max_chars: Option<usize>,
+    pub max_bytes: Option<usize>,
+    pub max_items: Option<usize>,
+}
+
+impl ValueCapture {
+    /// Parse a `value(...)` option of `#[valust(...)]` into `slot`.
+    pub fn parse_option(
+        meta: ParseNestedMeta,
+        slot: &mut Option<Self>,
+    ) -> syn::Result<()> {
+        if slot.is_some() {
+            return Err(meta.error("found multiple `value` options"));
+        }
+        *slot = Some(Self::parse_nested(meta)?);
+        Ok(())
+    }
+
+    /// Parse the inner part of `value(...)`.
+    fn parse_nested(meta: ParseNestedMeta) -> syn::Result<Self> {
+        let mut capture = Self::default();
+        meta.parse_nested_meta(|inner| {
+            let style = if inner.path.is_ident("debug") {
+                CaptureStyle::Debug
+            } else if inner.path.is_ident("display") {
+                CaptureStyle::Display
+            } else if inner.path.is_ident("none") {
+                CaptureStyle::Ignore
+            } else if inner.path.is_ident("with") {
+                CaptureStyle::With(inner.value()?.parse()?)
+            } else if inner.path.is_ident("max_chars") {
+                return set_limit(&inner, &mut capture.max_chars);
+            } else if inner.path.is_ident("max_bytes") {
+                return set_limit(&inner, &mut capture.max_bytes);
+            } else if inner.path.is_ident("max_items") {
+                return set_limit(&inner, &mut capture.max_items);
+            } else {
+                return Err(inner.error(VALUE_CAPTURE_ERR));
+            };
+            if capture.style.replace(style).is_some() {
+                return Err(inner.error("found multiple value capture policies"));
+            }
+            Ok(())
+        })?;
+        Ok(capture)
+    }
+
+    /// Fill unset items with `default`'s.
+    pub fn or(self, default: &Self) -> Self {
+        Self {
+            style: self.style.or_else(|| default.style.clone()),
+            max_chars: self.max_chars.or(default.max_chars),
+            max_bytes: self.max_bytes.or(default.max_bytes),
+            max_items: self.max_items.or(default.max_items),
+        }
+    }
+
+    /// Whether the policy needs to read the value at all.
+    pub fn needs_value(&self) -> bool {
+        !matches!(self.style, Some(CaptureStyle::Ignore))
+    }
+
+    /// Generate the `Option<fmt::Arguments>` and `Option<usize>` (length)
+    /// expressions passed to the runtime error helpers.
+    pub fn gen_value(&self, value: &Ident) -> (TokenStream, TokenStream) {
+        let args = match self.style.as_ref().unwrap_or(&CaptureStyle::Debug) {
+            CaptureStyle::Debug => quote! { format_args!("{:?}", #value) },
+            CaptureStyle::Display => quote! { format_args!("{}", #value) },
+            CaptureStyle::Ignore => {
+                return (
+                    quote! { ::core::option::Option::None },
+                    quote! { ::core::option::Option::None },
+                );
+            }
+            CaptureStyle::With(func) => {
+                quote! { format_args!("{}", (#func)(&#value)) }
+            }
+        };
+        (
+            quote! { ::core::option::Option::Some(#args) },
+            quote! { (&&::valust::__private::LenProbe(&#value)).valust_len() },
+        )
+    }
+
+    /// Generate the `ValueLimits` constant.
+    pub fn gen_limits(&self) -> TokenStream {
+        if self.max_chars.is_none()
+            && self.max_bytes.is_none()
+            && self.max_items.is_none()
+        {
+            return quote! { ::valust::error::value::ValueLimits::UNLIMITED };
+        }
+        let limit = |limit: Option<usize>| match limit {
+            Some(limit) => quote! { ::core::option::Option::Some(#limit) },
+            None => quote! { ::core::option::Option::None },
+        };
+        let max_chars = limit(self.max_chars);
+        let max_bytes = limit(self.max_bytes);
+        let max_items = limit(self.max_items);
+
+        quote! {
+            ::valust::error::value::ValueLimits {
+                max_chars: #max_chars,
+                max_bytes: #max_bytes,
+                max_items: #max_items,
+            }
+        }
+    }
+}
+
+fn set_limit(meta: &ParseNestedMeta, limit: &mut Option<usize>) -> syn::Result<()> {
+    let lit: LitInt = meta.value()?.parse()?;
+    if limit.replace(lit.base10_parse()?).is_some() {
+        return Err(meta.error("found multiple conflicting limits"));
+    }
+    Ok(())
+}
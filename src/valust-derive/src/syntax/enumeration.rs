@@ -0,0 +1,204 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, Meta};
+
+use super::serde::{RenameRule, find_serde_name, find_serde_names};
+use crate::utils::error::SyntaxError;
+
+const UNSUPPORTED_ENUM_TYPE: &str = "\
+    Unsupported type. \
+    `ValustEnum` only supports enums with unit variants.";
+
+const META_SYNTAX_ERR_VALUST: &str = "\
+    Invalid `valust` usage.\n\
+    For usages, refer to the crate's doc.";
+
+/// A string-backed enum of `#[derive(ValustEnum)]`.
+pub struct Enumeration {
+    input: DeriveInput,
+    case_insensitive: bool,
+    variants: Vec<Variant>,
+}
+
+struct Variant {
+    ident: Ident,
+    /// Name shown in errors.
+    name: String,
+    aliases: Vec<String>,
+}
+
+impl Enumeration {
+    pub fn from_input(input: DeriveInput) -> syn::Result<Self> {
+        let Data::Enum(data) = &input.data else {
+            return Err(syn::Error::new(input.ident.span(), UNSUPPORTED_ENUM_TYPE));
+        };
+
+        let mut case_insensitive = false;
+        let mut rename_all = None;
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("valust")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("case_insensitive") {
+                    case_insensitive = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename_all") {
+                    rename_all = Some(RenameRule::parse(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else {
+                    Err(meta.error(META_SYNTAX_ERR_VALUST))
+                }
+            })?;
+        }
+        let serde_attrs = input.attrs.iter().map(|a| &a.meta).collect::<Vec<_>>();
+        let rename_all = match rename_all {
+            Some(rule) => Some(rule),
+            None => find_serde_name(serde_attrs.iter().copied(), "rename_all")
+                .map(|lit| RenameRule::parse(&lit))
+                .transpose()?,
+        };
+
+        let mut err = SyntaxError::new();
+        let mut variants = Vec::with_capacity(data.variants.len());
+        for variant in &data.variants {
+            if !matches!(variant.fields, Fields::Unit) {
+                err.push(syn::Error::new(variant.ident.span(), UNSUPPORTED_ENUM_TYPE));
+                continue;
+            }
+            match Variant::from_input(variant, rename_all) {
+                Ok(v) => variants.push(v),
+                Err(e) => err.push(e),
+            }
+        }
+        err.check()?;
+
+        let this = Self {
+            input,
+            case_insensitive,
+            variants,
+        };
+        this.check_duplicates()?;
+        Ok(this)
+    }
+
+    fn check_duplicates(&self) -> syn::Result<()> {
+        let mut seen = Vec::<String>::new();
+        let mut err = SyntaxError::new();
+        for variant in &self.variants {
+            for name in variant.names() {
+                let key = if self.case_insensitive {
+                    name.to_ascii_lowercase()
+                } else {
+                    name.to_owned()
+                };
+                if seen.contains(&key) {
+                    err.push(syn::Error::new(
+                        variant.ident.span(),
+                        format!("found multiple variants named `{}`", name),
+                    ));
+                } else {
+                    seen.push(key);
+                }
+            }
+        }
+        err.check()
+    }
+
+    pub fn gen_from_str_impl(&self) -> TokenStream {
+        let ty = &self.input.ident;
+        let ty_text = ty.to_string();
+        let (impl_generics, ty_generics, where_clause) =
+            self.input.generics.split_for_impl();
+        let expected = self.variants.iter().map(|v| &v.name);
+
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let names = variant.names();
+            if self.case_insensitive {
+                quote! {
+                    if #(s.eq_ignore_ascii_case(#names))||* {
+                        return ::core::result::Result::Ok(Self::#ident);
+                    }
+                }
+            } else {
+                quote! {
+                    if #(s == #names)||* {
+                        return ::core::result::Result::Ok(Self::#ident);
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::str::FromStr for #ty #ty_generics #where_clause {
+                type Err = ::valust::error::enums::ParseEnumError;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    #(#arms)*
+                    ::core::result::Result::Err(::valust::error::enums::ParseEnumError {
+                        enum_name: #ty_text,
+                        input: ::core::convert::Into::into(s),
+                        expected: &[#(#expected),*],
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Variant {
+    fn from_input(
+        variant: &syn::Variant,
+        rename_all: Option<RenameRule>,
+    ) -> syn::Result<Self> {
+        let mut rename: Option<LitStr> = None;
+        let mut aliases = Vec::new();
+        for attr in variant.attrs.iter().filter(|a| a.path().is_ident("valust")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    if rename.is_some() {
+                        return Err(meta.error("found multiple `rename` options"));
+                    }
+                    rename = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("alias") {
+                    aliases.push(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else {
+                    Err(meta.error(META_SYNTAX_ERR_VALUST))
+                }
+            })?;
+        }
+        let serde_attrs = variant
+            .attrs
+            .iter()
+            .map(|a| &a.meta)
+            .collect::<Vec<&Meta>>();
+        let name = rename
+            .or_else(|| find_serde_name(serde_attrs.iter().copied(), "rename"))
+            .map(|lit| lit.value())
+            .unwrap_or_else(|| {
+                let ident = variant.ident.to_string();
+                match rename_all {
+                    Some(rule) => rule.apply_to_variant(&ident),
+                    None => ident,
+                }
+            });
+        aliases.extend(
+            find_serde_names(serde_attrs.iter().copied(), "alias")
+                .iter()
+                .map(LitStr::value),
+        );
+
+        Ok(Self {
+            ident: variant.ident.clone(),
+            name,
+            aliases,
+        })
+    }
+
+    fn names(&self) -> Vec<&str> {
+        std::iter::once(self.name.as_str())
+            .chain(self.aliases.iter().map(String::as_str))
+            .collect()
+    }
+}
@@ -1,9 +1,15 @@
 use proc_macro2::{Literal, Span, TokenStream};
-use quote::{format_ident, quote, quote_spanned};
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Ident, Type, Visibility};
+use syn::{Ident, LitStr, Meta, Token, Type, Visibility};
 
+use super::capture::ValueCapture;
+use super::field_attr::valust::{FieldOptions, parse_valust};
 use super::field_attr::{FIELD_ATTRS, FieldHandler};
+use super::serde::find_serde_name;
+use super::struct_attr::StructAttr;
 use crate::utils::error::SyntaxError;
 
 #[derive(Debug, Clone)]
@@ -40,24 +46,72 @@ impl FieldName {
     }
 }
 
+/// Field-wide settings shared by all operations of a field.
+pub struct FieldContext {
+    pub value: ValueCapture,
+    /// External name of the field, used in error `field`s and `path`s.
+    pub key: String,
+    /// `valust(label = "...")` of the field.
+    pub label: Option<String>,
+    /// Whether the raw structure uses serde, by forwarding a serde derive or
+    /// attribute.
+    pub serde: bool,
+}
+
+impl FieldContext {
+    /// Label stored on errors, which defaults to the external name.
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.key)
+    }
+
+    /// Placeholder of the label in default messages.
+    ///
+    /// The label is filled in at runtime, so that it can be translated.
+    pub fn label_slot(&self) -> &'static str {
+        if self.label.is_some() {
+            "{label}"
+        } else {
+            "`{label}`"
+        }
+    }
+}
+
 pub struct Field {
     pub vis: Visibility,
     pub name: FieldName,
     pub ty: Type,
     pub operations: Vec<Box<dyn FieldHandler>>,
+    pub options: FieldOptions,
+    /// `serde(rename = "...")` forwarded to the raw field.
+    pub serde_rename: Option<LitStr>,
 }
 
 impl Field {
     pub fn from_input(s: syn::Field, index: usize) -> syn::Result<Self> {
-        Ok(Self {
+        let mut options = FieldOptions::default();
+        let mut forwarded = Vec::new();
+        let field = Self {
             operations: {
                 let mut attrs = Vec::new();
                 let mut err = SyntaxError::new();
                 for attr in s.attrs {
-                    if let Some(cmd) = FIELD_ATTRS
+                    if attr.path().is_ident("valust") {
+                        if let Err(e) = parse_valust(&attr.meta, &mut options) {
+                            err.push(e);
+                        }
+                    } else if let Some(cmd) = FIELD_ATTRS
                         .iter()
                         .find(|cmd| attr.path().is_ident(cmd.ident()))
                     {
+                        if attr.path().is_ident("forward_attr") {
+                            forwarded.extend(
+                                attr.parse_args_with(
+                                    Punctuated::<Meta, Token![,]>::parse_terminated,
+                                )
+                                .into_iter()
+                                .flatten(),
+                            );
+                        }
                         let handler = cmd.parse(&s.ty, attr.meta);
                         match handler {
                             Ok(h) => attrs.push(h),
@@ -73,7 +127,123 @@ impl Field {
                 .ident
                 .map_or(FieldName::UnNamed(index, s.ty.span()), FieldName::Named),
             ty: s.ty,
-        })
+            options,
+            serde_rename: find_serde_name(&forwarded, "rename"),
+        };
+        field.check_options()?;
+        Ok(field)
+    }
+
+    fn check_options(&self) -> syn::Result<()> {
+        let message = if self.options.computed.is_some() && self.options.skip {
+            "a field cannot be both `computed` and `skip`ped"
+        } else if self.options.default.is_some() && !self.options.skip {
+            "`default` is only allowed on `skip`ped fields"
+        } else if !self.is_raw() && !self.operations.is_empty() {
+            "`computed` or `skip`ped fields cannot have other operations"
+        } else {
+            return Ok(());
+        };
+        Err(syn::Error::new(self.name.span(), message))
+    }
+
+    /// Whether the field is part of the raw data structure.
+    pub fn is_raw(&self) -> bool {
+        self.options.computed.is_none() && !self.options.skip
+    }
+
+    /// Generate the binding of a field that is not part of the raw data
+    /// structure.
+    pub fn gen_derived(&self) -> Option<TokenStream> {
+        let ident = self.name.name();
+        let ty = &self.ty;
+        let value = if let Some(computed) = &self.options.computed {
+            quote! { #computed }
+        } else if self.options.skip {
+            self.options.default.as_ref().map_or_else(
+                || quote! { ::core::default::Default::default() },
+                |d| quote! { #d },
+            )
+        } else {
+            return None;
+        };
+        Some(quote! { let #ident: #ty = #value; })
+    }
+
+    /// Generate whether the field of the structure `owner` may have
+    /// identifiers or references, or `None` if it never has any.
+    pub fn gen_has_refs(&self, owner: &Ident) -> Option<TokenStream> {
+        if self.options.id || self.options.references.is_some() {
+            return Some(quote! { true });
+        }
+        let nested = self
+            .operations
+            .iter()
+            .filter_map(|op| op.gen_has_refs(owner))
+            .collect::<Vec<_>>();
+        (!nested.is_empty()).then(|| quote! { #(#nested)||* })
+    }
+
+    /// Generate the collection of the field's identifiers and references in
+    /// `Validate::collect_refs`.
+    pub fn gen_collect_refs(&self, ctx: &FieldContext) -> TokenStream {
+        let key = self.name.struct_key();
+        let field_text = &ctx.key;
+        let label = ctx.label();
+        let slot = ctx.label_slot();
+        let type_name = self.ty.to_token_stream().to_string();
+        let path =
+            quote! { ::valust::refs::RefCollector::join(valust_path, #field_text) };
+
+        let id = self.options.id.then(|| {
+            let message = format!("{} is a duplicate id", slot);
+            quote! {
+                valust_refs.push_id(
+                    &::valust::refs::RefSite {
+                        field: #field_text,
+                        label: #label,
+                        message: #message,
+                        expression: "id",
+                        type_name: #type_name,
+                    },
+                    valust_collection,
+                    &self.#key,
+                    #path,
+                );
+            }
+        });
+        let references = self.options.references.as_ref().map(|collection| {
+            let message = format!(
+                "{} references an unknown id in `{}`",
+                slot,
+                collection.value()
+            );
+            let expression = format!("references = {:?}", collection.value());
+            quote! {
+                valust_refs.push_ref(
+                    &::valust::refs::RefSite {
+                        field: #field_text,
+                        label: #label,
+                        message: #message,
+                        expression: #expression,
+                        type_name: #type_name,
+                    },
+                    #collection,
+                    &self.#key,
+                    #path,
+                );
+            }
+        });
+        let nested = self
+            .operations
+            .iter()
+            .filter_map(|op| op.gen_collect_refs(&self.name, ctx));
+
+        quote! {
+            #id
+            #references
+            #(#nested)*
+        }
     }
 
     fn infer_in_type(&self) -> Option<Type> {
@@ -91,7 +261,46 @@ impl Field {
         self.infer_in_type().unwrap_or(self.ty.clone())
     }
 
-    pub fn gen_validate_func(&self) -> syn::Result<(Ident, TokenStream)> {
+    /// External name of the field.
+    ///
+    /// `valust(rename)` takes precedence over serde's `rename`, which takes
+    /// precedence over serde's `rename_all`.
+    pub fn key(&self, attrs: &StructAttr) -> String {
+        if let Some(name) = self.options.rename.as_ref().or(self.serde_rename.as_ref())
+        {
+            return name.value();
+        }
+        match &self.name {
+            FieldName::Named(name) => {
+                let name = name.unraw().to_string();
+                match attrs.rename_all {
+                    Some(rule) => rule.apply_to_field(&name),
+                    None => name,
+                }
+            }
+            FieldName::UnNamed(idx, _) => idx.to_string(),
+        }
+    }
+
+    pub fn context(&self, attrs: &StructAttr) -> FieldContext {
+        let default_value = attrs.options.value.clone().unwrap_or_default();
+        FieldContext {
+            value: self
+                .options
+                .value
+                .clone()
+                .unwrap_or_default()
+                .or(&default_value),
+            key: self.key(attrs),
+            label: self.options.label.as_ref().map(LitStr::value),
+            serde: attrs.uses_serde(),
+        }
+    }
+
+    pub fn gen_validate_func(
+        &self,
+        ctx: &FieldContext,
+    ) -> syn::Result<(Ident, TokenStream)> {
         let in_type = self.infer_in_type().unwrap_or(self.ty.clone());
         let out_type = self.infer_out_type().unwrap_or(self.ty.clone());
         let field_ident = &self.name.name();
@@ -107,7 +316,7 @@ impl Field {
         let block = self
             .operations
             .iter()
-            .map(|item| item.gen_expr(&err_ident, &self.name))
+            .map(|item| item.gen_expr(&err_ident, &self.name, ctx))
             .filter_map(|code| match code {
                 Ok(code) => Some(code),
                 Err(err) => {
@@ -123,7 +332,7 @@ impl Field {
         );
 
         let expanded = quote! {
-            fn #func_name(#field_ident: #in_type, #err_ident: &mut ::valust::error::ValidationError) -> Option<#out_type> {
+            fn #func_name(#field_ident: #in_type, #err_ident: &mut ::valust::error::ValidationError, valust_depth: usize) -> Option<#out_type> {
                 #(#block)*
                 Some(#field_ident)
             }
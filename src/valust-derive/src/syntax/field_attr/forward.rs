@@ -1,14 +1,16 @@
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{ToTokens, quote};
+use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
-use syn::{Ident, Meta, Type, parse_quote};
+use syn::{Ident, Meta, Token, Type, parse_quote};
 
 use super::{FieldCommand, FieldHandler};
-use crate::syntax::field::FieldName;
+use crate::syntax::field::{FieldContext, FieldName};
 
 const META_SYNTAX_ERR: &str = "\
     Invalid `forward` usage.\n\
-    Expect to be `#[forward]` or `#[forward(Type)]`.";
+    Expect to be `#[forward]`, `#[forward(Type)]`, `#[forward(flatten)]` \
+    or `#[forward(flatten, Type)]`.";
 
 pub struct Forward;
 
@@ -19,17 +21,53 @@ impl FieldCommand for Forward {
 
     fn parse(&self, ty: &Type, meta: Meta) -> syn::Result<Box<dyn FieldHandler>> {
         match meta {
-            Meta::Path(_) => Ok(Box::new(ForwardCmdHandler { ty: ty.clone() })),
-            Meta::List(lst) => Ok(Box::new(ForwardCmdHandler {
-                ty: lst.parse_args()?,
+            Meta::Path(_) => Ok(Box::new(ForwardCmdHandler {
+                ty: ty.clone(),
+                flatten: false,
             })),
+            Meta::List(lst) => {
+                let args = lst.parse_args::<ForwardArgs>()?;
+                Ok(Box::new(ForwardCmdHandler {
+                    ty: args.ty.unwrap_or_else(|| ty.clone()),
+                    flatten: args.flatten,
+                }))
+            }
             _ => Err(syn::Error::new(meta.path().span(), META_SYNTAX_ERR)),
         }
     }
 }
 
+/// Arguments of `forward(...)`: an optional `flatten` flag, then the type.
+struct ForwardArgs {
+    flatten: bool,
+    ty: Option<Type>,
+}
+
+impl Parse for ForwardArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+        let flatten = fork.parse::<Ident>().is_ok_and(|ident| ident == "flatten")
+            && (fork.is_empty() || fork.peek(Token![,]));
+        if flatten {
+            input.parse::<Ident>()?;
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        let ty = if input.is_empty() {
+            None
+        } else {
+            Some(input.parse()?)
+        };
+        Ok(Self { flatten, ty })
+    }
+}
+
 pub struct ForwardCmdHandler {
     ty: Type,
+    /// Merge the errors of the field without prefixing their paths, and
+    /// forward `serde(flatten)` to the raw field if serde is used.
+    flatten: bool,
 }
 
 impl FieldHandler for ForwardCmdHandler {
@@ -42,19 +80,77 @@ impl FieldHandler for ForwardCmdHandler {
         Some(self.ty.clone())
     }
 
-    fn gen_expr(&self, err: &Ident, field: &FieldName) -> syn::Result<TokenStream> {
+    fn gen_expr(
+        &self,
+        err: &Ident,
+        field: &FieldName,
+        ctx: &FieldContext,
+    ) -> syn::Result<TokenStream> {
         let field_ident = field.name();
-        let field_text = field.struct_key().to_string();
+        let field_text = &ctx.key;
         let out_type = &self.ty;
+        let extend = if self.flatten {
+            quote! { #err.append_error(e_valust) }
+        } else {
+            quote! { #err.extend_error(#field_text, e_valust) }
+        };
 
         Ok(quote! {
-            let #field_ident: #out_type = match ::valust::Validate::validate(#field_ident) {
+            let #field_ident: #out_type = match ::valust::Validate::validate_nested(#field_ident, valust_depth + 1) {
                 Ok(v_valust) => v_valust,
                 Err(e_valust) => {
-                    #err.extend_error(#field_text, e_valust);
+                    #extend;
                     return None;
                 },
             };
         })
     }
+
+    fn gen_has_refs(&self, owner: &Ident) -> Option<TokenStream> {
+        // a recursive field has references only if the rest of its structure
+        // has, and its `HAS_REFS` would be a cycle
+        if mentions(self.ty.to_token_stream(), owner) {
+            return None;
+        }
+        let ty = &self.ty;
+        Some(quote! { <#ty as ::valust::Validate>::HAS_REFS })
+    }
+
+    fn gen_collect_refs(
+        &self,
+        field: &FieldName,
+        ctx: &FieldContext,
+    ) -> Option<TokenStream> {
+        let key = field.struct_key();
+        let field_text = &ctx.key;
+        let (path, collection) = if self.flatten {
+            (quote! { valust_path }, quote! { valust_collection })
+        } else {
+            (
+                quote! { &::valust::refs::RefCollector::join(valust_path, #field_text) },
+                quote! { #field_text },
+            )
+        };
+        Some(quote! {
+            ::valust::Validate::collect_refs(&self.#key, #path, #collection, valust_refs);
+        })
+    }
+
+    /// `serde(flatten)`, if the raw structure uses serde.
+    fn gen_raw_attr(
+        &self,
+        _field: &FieldName,
+        ctx: &FieldContext,
+    ) -> Option<TokenStream> {
+        (self.flatten && ctx.serde).then(|| quote! { #[serde(flatten)] })
+    }
+}
+
+/// Whether `tokens` refer to the structure `owner`, by name or as `Self`.
+fn mentions(tokens: TokenStream, owner: &Ident) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(ident) => ident == *owner || ident == "Self",
+        TokenTree::Group(group) => mentions(group.stream(), owner),
+        _ => false,
+    })
 }
@@ -4,7 +4,7 @@ use syn::punctuated::Punctuated;
 use syn::{Ident, Meta, Token, Type};
 
 use super::{FieldCommand, FieldHandler};
-use crate::syntax::field::FieldName;
+use crate::syntax::field::{FieldContext, FieldName};
 
 pub struct ForwardAttr;
 
@@ -34,11 +34,20 @@ impl FieldHandler for ForwardAttrHandler {
         None
     }
 
-    fn gen_expr(&self, _err: &Ident, _field: &FieldName) -> syn::Result<TokenStream> {
+    fn gen_expr(
+        &self,
+        _err: &Ident,
+        _field: &FieldName,
+        _ctx: &FieldContext,
+    ) -> syn::Result<TokenStream> {
         Ok(Default::default())
     }
 
-    fn gen_raw_attr(&self, _field: &FieldName) -> Option<TokenStream> {
+    fn gen_raw_attr(
+        &self,
+        _field: &FieldName,
+        _ctx: &FieldContext,
+    ) -> Option<TokenStream> {
         let attr = self.attrs.iter();
         Some(quote! {
             #(
@@ -1,12 +1,13 @@
 use proc_macro2::TokenStream;
 use syn::{Ident, Meta, Type};
 
-use super::field::FieldName;
+use super::field::{FieldContext, FieldName};
 
 mod forward;
 mod forward_attr;
 mod trans;
 mod valid;
+pub mod valust;
 
 pub const FIELD_ATTRS: &[&dyn FieldCommand] = &[
     &forward::Forward,
@@ -26,11 +27,35 @@ pub trait FieldHandler {
 
     fn out_type(&self) -> Option<Type>;
 
-    fn gen_expr(&self, err: &Ident, field: &FieldName) -> syn::Result<TokenStream>;
+    fn gen_expr(
+        &self,
+        err: &Ident,
+        field: &FieldName,
+        ctx: &FieldContext,
+    ) -> syn::Result<TokenStream>;
 
     // generate attr over raw item.
     // with `#[]` wrapper
-    fn gen_raw_attr(&self, _field: &FieldName) -> Option<TokenStream> {
+    fn gen_raw_attr(
+        &self,
+        _field: &FieldName,
+        _ctx: &FieldContext,
+    ) -> Option<TokenStream> {
+        None
+    }
+
+    // generate whether a nested value may have references, i.e. its
+    // `Validate::HAS_REFS`, for a field of the structure `owner`.
+    fn gen_has_refs(&self, _owner: &Ident) -> Option<TokenStream> {
+        None
+    }
+
+    // generate the `Validate::collect_refs` call of a nested value.
+    fn gen_collect_refs(
+        &self,
+        _field: &FieldName,
+        _ctx: &FieldContext,
+    ) -> Option<TokenStream> {
         None
     }
 }
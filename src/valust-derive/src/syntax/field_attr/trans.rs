@@ -5,8 +5,11 @@ use syn::{Ident, Meta, Type};
 
 use super::{FieldCommand, FieldHandler};
 use crate::cmd::trans::{TRANS_COMMANDS, TransHandler};
-use crate::syntax::field::FieldName;
-use crate::utils::create_error::create_transform_error;
+use crate::syntax::field::{FieldContext, FieldName};
+use crate::utils::create_error::{
+    create_captures_error, create_snapshot, create_transform_error,
+    create_transformer_error,
+};
 use crate::utils::error::SyntaxError;
 
 const META_SYNTAX_ERR: &str = "\
@@ -64,11 +67,19 @@ pub struct TransCmdHandler {
 
 impl FieldHandler for TransCmdHandler {
     fn in_type(&self) -> Option<Type> {
+        // without declared types, infer the input type back from the field type
         Some(
             self.handlers
                 .iter()
                 .find_map(|handler| handler.in_type())
-                .unwrap_or(self.ty.clone()),
+                .unwrap_or_else(|| {
+                    self.handlers
+                        .iter()
+                        .rev()
+                        .fold(self.ty.clone(), |ty, handler| {
+                            handler.in_type_for(&ty).unwrap_or(ty)
+                        })
+                }),
         )
     }
 
@@ -82,7 +93,12 @@ impl FieldHandler for TransCmdHandler {
         )
     }
 
-    fn gen_expr(&self, err: &Ident, field: &FieldName) -> syn::Result<TokenStream> {
+    fn gen_expr(
+        &self,
+        err: &Ident,
+        field: &FieldName,
+        ctx: &FieldContext,
+    ) -> syn::Result<TokenStream> {
         let (_, expanded) = self.handlers.iter().fold(
             (None, TokenStream::new()),
             |(prev_ty, mut acc), h| {
@@ -90,7 +106,7 @@ impl FieldHandler for TransCmdHandler {
                 let out_ty = h.out_type();
                 let ident = field.name();
                 let expr = h.gen_transformer_expr(&ident);
-                let msg = h.message(&ident);
+                let msg = h.message(ctx.label_slot());
 
                 let decl = if let Some(ty) = &prev_ty {
                     quote! { let #ident: #ty }
@@ -98,41 +114,129 @@ impl FieldHandler for TransCmdHandler {
                     quote! { let #ident }
                 };
 
-                let expanded = if h.is_fallible() {
-                    let cause = format_ident!(
-                        "valust_trans_err_cause",
+                let expanded = if h.applies_transformer() {
+                    let failure = format_ident!(
+                        "valust_trans_failure",
                         span = in_ty
                             .as_ref()
                             .or(prev_ty.as_ref())
                             .unwrap_or(&self.ty)
                             .span()
                     );
-                    let (pre_trans, trans_fmt) = create_transform_error(
+                    let (snapshot, pre_trans) =
+                        create_snapshot(&ident, field, ctx).unzip();
+                    let trans_fmt = create_transformer_error(
                         err,
-                        field,
-                        &cause,
+                        &failure,
                         msg,
                         &expr,
-                        (prev_ty.as_ref().or(in_ty.as_ref()), out_ty.as_ref()),
-                        true,
+                        snapshot.as_ref(),
+                        ctx,
                     );
 
-                    let pre_trans = if let Some(pre) = pre_trans {
-                        quote! { let #pre = #ident.clone(); }
-                    } else {
-                        quote! {}
-                    };
-
                     quote! {{
                         #pre_trans
-                        match (#expr) {
-                            ::std::result::Result::Ok(valust_v) => valust_v,
-                            ::std::result::Result::Err(#cause) => {
+                        match ::valust::__private::apply_transformer(&(#expr), #ident) {
+                            ::core::result::Result::Ok(valust_v) => valust_v,
+                            ::core::result::Result::Err(#failure) => {
                                 #trans_fmt;
                                 return None;
                             }
                         }
                     }}
+                } else if h.parses_captures() {
+                    let failure = format_ident!("valust_trans_failure");
+                    let expr_text =
+                        h.gen_expr_display().unwrap_or_else(|| expr.to_string());
+                    let trans_fmt = create_captures_error(
+                        err,
+                        field,
+                        &failure,
+                        msg,
+                        expr_text,
+                        out_ty.as_ref(),
+                        ctx,
+                    );
+
+                    quote! {
+                        match (#expr) {
+                            ::core::result::Result::Ok(valust_v) => valust_v,
+                            ::core::result::Result::Err(#failure) => {
+                                #trans_fmt;
+                                return None;
+                            }
+                        }
+                    }
+                } else if h.is_fallible() {
+                    let cause = format_ident!(
+                        "valust_trans_err_cause",
+                        span = in_ty
+                            .as_ref()
+                            .or(prev_ty.as_ref())
+                            .unwrap_or(&self.ty)
+                            .span()
+                    );
+                    let types = (prev_ty.as_ref().or(in_ty.as_ref()), out_ty.as_ref());
+
+                    if h.returns_input() {
+                        // the input is handed back on failure, and only captured then
+                        let input = format_ident!("valust_trans_input_{}", ident);
+                        let (snapshot, capture) =
+                            create_snapshot(&input, field, ctx).unzip();
+                        let input = if snapshot.is_some() {
+                            quote! { #input }
+                        } else {
+                            quote! { _ }
+                        };
+                        let trans_fmt = create_transform_error(
+                            err,
+                            &cause,
+                            msg,
+                            &expr,
+                            types,
+                            snapshot.as_ref(),
+                            ctx,
+                        );
+                        quote! {
+                            match (#expr) {
+                                ::core::result::Result::Ok(valust_v) => valust_v,
+                                ::core::result::Result::Err(
+                                    ::valust::error::transform::Rejected {
+                                        input: #input,
+                                        cause: #cause,
+                                    }
+                                ) => {
+                                    #capture
+                                    #trans_fmt;
+                                    return None;
+                                }
+                            }
+                        }
+                    } else {
+                        // captured without allocating, as the input is consumed
+                        let (snapshot, pre_trans) =
+                            create_snapshot(&ident, field, ctx).unzip();
+                        let trans_fmt = create_transform_error(
+                            err,
+                            &cause,
+                            msg,
+                            &expr,
+                            types,
+                            snapshot.as_ref(),
+                            ctx,
+                        );
+
+                        quote! {{
+                            #pre_trans
+                            match (#expr) {
+                                ::core::result::Result::Ok(valust_v) => valust_v,
+                                ::core::result::Result::Err(#cause) => {
+                                    #trans_fmt;
+                                    return None;
+                                }
+                            }
+                        }}
+                    }
                 } else {
                     quote! { (#expr) }
                 };
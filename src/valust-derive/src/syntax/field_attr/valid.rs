@@ -5,8 +5,8 @@ use syn::{Ident, Meta, Type};
 
 use super::{FieldCommand, FieldHandler};
 use crate::cmd::valid::{VALID_COMMANDS, ValidHandler};
-use crate::syntax::field::FieldName;
-use crate::utils::create_error::create_validate_error;
+use crate::syntax::field::{FieldContext, FieldName};
+use crate::utils::create_error::{create_validate_error, create_violation_error};
 use crate::utils::error::SyntaxError;
 
 const META_SYNTAX_ERR: &str = "\
@@ -71,12 +71,18 @@ impl FieldHandler for ValidCmdHandler {
         Some(self.ty.clone())
     }
 
-    fn gen_expr(&self, err: &Ident, field: &FieldName) -> syn::Result<TokenStream> {
+    fn gen_expr(
+        &self,
+        err: &Ident,
+        field: &FieldName,
+        ctx: &FieldContext,
+    ) -> syn::Result<TokenStream> {
         let code = self.handlers.iter().map(|t| {
             let expr = t.gen_validator_expr(&field.name());
             let expr_text = t.gen_expr_display(&field.name())
                     .unwrap_or_else(|| expr.to_token_stream().to_string());
-            let msg = t.message(&field.name());
+            let msg = t.message(ctx.label_slot());
+            let params = t.params();
             let invalid_err = create_validate_error(
                 err,
                 field,
@@ -84,10 +90,24 @@ impl FieldHandler for ValidCmdHandler {
                 msg,
                 &expr_text,
                 &self.ty,
-                true,
+                ctx,
+                &params,
             );
 
-            if t.is_fallible() {
+            if t.reports_violation() {
+                let violation =
+                    format_ident!("valust_valid_violation", span = self.ty.span());
+                let violation_err = create_violation_error(
+                    err, field, &violation, &expr_text, &self.ty, ctx,
+                );
+
+                quote! {
+                    if let ::core::result::Result::Err(#violation) = (#expr) {
+                        #violation_err;
+                        return None;
+                    }
+                }
+            } else if t.is_fallible() {
                 let cause =
                     format_ident!("valust_valid_err_cause", span = self.ty.span());
                 let fail_err = create_validate_error(
@@ -97,14 +117,15 @@ impl FieldHandler for ValidCmdHandler {
                     None,
                     &expr_text,
                     &self.ty,
-                    true,
+                    ctx,
+                    &params,
                 );
 
                 quote! {
                     match (#expr) {
-                        ::std::result::Result::Ok(true) => {},
-                        ::std::result::Result::Ok(false) => { #invalid_err; return None; },
-                        ::std::result::Result::Err(#cause) => { #fail_err; return None; },
+                        ::core::result::Result::Ok(true) => {},
+                        ::core::result::Result::Ok(false) => { #invalid_err; return None; },
+                        ::core::result::Result::Err(#cause) => { #fail_err; return None; },
                     }
                 }
             } else {
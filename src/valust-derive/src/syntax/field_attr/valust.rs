@@ -0,0 +1,72 @@
+use syn::{Expr, LitStr, Meta};
+
+use crate::syntax::capture::ValueCapture;
+
+const META_SYNTAX_ERR_VALUST: &str = "\
+    Invalid `valust` usage.\n\
+    For usages, refer to the crate's doc.";
+
+#[derive(Default)]
+pub struct FieldOptions {
+    pub value: Option<ValueCapture>,
+    pub rename: Option<LitStr>,
+    pub label: Option<LitStr>,
+    pub computed: Option<Expr>,
+    pub skip: bool,
+    pub default: Option<Expr>,
+    pub id: bool,
+    pub references: Option<LitStr>,
+}
+
+pub fn parse_valust(meta: &Meta, options: &mut FieldOptions) -> syn::Result<()> {
+    let lst = meta.require_list()?;
+    lst.parse_nested_meta(|meta| {
+        if meta.path.is_ident("value") {
+            ValueCapture::parse_option(meta, &mut options.value)
+        } else if meta.path.is_ident("rename") {
+            if options.rename.is_some() {
+                return Err(meta.error("found multiple `rename` options"));
+            }
+            options.rename = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("label") {
+            if options.label.is_some() {
+                return Err(meta.error("found multiple `label` options"));
+            }
+            options.label = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("computed") {
+            if options.computed.is_some() {
+                return Err(meta.error("found multiple `computed` options"));
+            }
+            options.computed = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("skip") {
+            if options.skip {
+                return Err(meta.error("found multiple `skip` options"));
+            }
+            options.skip = true;
+            Ok(())
+        } else if meta.path.is_ident("default") {
+            if options.default.is_some() {
+                return Err(meta.error("found multiple `default` options"));
+            }
+            options.default = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("id") {
+            if options.id {
+                return Err(meta.error("found multiple `id` options"));
+            }
+            options.id = true;
+            Ok(())
+        } else if meta.path.is_ident("references") {
+            if options.references.is_some() {
+                return Err(meta.error("found multiple `references` options"));
+            }
+            options.references = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error(META_SYNTAX_ERR_VALUST))
+        }
+    })
+}
@@ -0,0 +1,72 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Fields};
+
+use crate::utils::types::option_inner;
+
+const UNSUPPORTED_CAPTURES_TYPE: &str = "\
+    Unsupported type. \
+    `FromCaptures` only supports structs with named fields.";
+
+/// A structure of `#[derive(FromCaptures)]`.
+///
+/// Each field is parsed from the regex group of the same name, and `Option`
+/// fields from optional groups.
+pub struct Captures {
+    input: DeriveInput,
+}
+
+impl Captures {
+    pub fn from_input(input: DeriveInput) -> syn::Result<Self> {
+        match &input.data {
+            Data::Struct(st) if matches!(st.fields, Fields::Named(_)) => {
+                Ok(Self { input })
+            }
+            _ => Err(syn::Error::new(
+                input.ident.span(),
+                UNSUPPORTED_CAPTURES_TYPE,
+            )),
+        }
+    }
+
+    pub fn gen_from_captures_impl(&self) -> TokenStream {
+        let Data::Struct(st) = &self.input.data else {
+            unreachable!("checked in `from_input`")
+        };
+        let name = &self.input.ident;
+        let (impl_generics, ty_generics, where_clause) =
+            self.input.generics.split_for_impl();
+
+        let fields = st.fields.iter().filter_map(|f| f.ident.as_ref());
+        let groups = fields.clone().map(|ident| ident.unraw().to_string());
+        let parses = st.fields.iter().filter_map(|f| {
+            let ident = f.ident.as_ref()?;
+            let group = ident.unraw().to_string();
+            let parse = if option_inner(&f.ty).is_some() {
+                quote! { parse_optional_group }
+            } else {
+                quote! { parse_group }
+            };
+            Some(quote! {
+                let #ident = ::valust::captures::#parse(valust_groups, #group, valust_failures);
+            })
+        });
+        let unwraps = fields.map(|ident| quote! { #ident: #ident? });
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::valust::captures::FromCaptures for #name #ty_generics #where_clause {
+                const GROUPS: &'static [&'static str] = &[#(#groups),*];
+
+                fn from_captures<'valust_h>(
+                    valust_groups: &dyn Fn(&'static str) -> ::core::option::Option<&'valust_h str>,
+                    valust_failures: &mut ::valust::__private::Vec<::valust::captures::CaptureFailure>,
+                ) -> ::core::option::Option<Self> {
+                    #(#parses)*
+                    ::core::option::Option::Some(Self { #(#unwraps,)* })
+                }
+            }
+        }
+    }
+}
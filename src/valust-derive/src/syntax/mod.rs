@@ -1,4 +1,8 @@
+pub mod capture;
+pub mod enumeration;
 pub mod field;
 mod field_attr;
+pub mod from_captures;
+mod serde;
 mod struct_attr;
 pub mod structure;
@@ -0,0 +1,152 @@
+use syn::punctuated::Punctuated;
+use syn::{Expr, ExprLit, Lit, LitStr, Meta, Token};
+
+const UNKNOWN_RENAME_RULE: &str = "\
+    Unknown serde rename rule.\n\
+    Expect one of `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, \
+    `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` or `SCREAMING-KEBAB-CASE`.";
+
+/// Serde's `rename_all` rules, applied to field names.
+#[derive(Clone, Copy)]
+#[allow(clippy::enum_variant_names)]
+pub enum RenameRule {
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    pub fn parse(lit: &LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => Self::LowerCase,
+            "UPPERCASE" => Self::UpperCase,
+            "PascalCase" => Self::PascalCase,
+            "camelCase" => Self::CamelCase,
+            "snake_case" => Self::SnakeCase,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnakeCase,
+            "kebab-case" => Self::KebabCase,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebabCase,
+            _ => return Err(syn::Error::new(lit.span(), UNKNOWN_RENAME_RULE)),
+        })
+    }
+
+    /// Apply the rule to a `snake_case` field name, the same way serde does.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::LowerCase | Self::SnakeCase => field.to_owned(),
+            Self::UpperCase | Self::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            Self::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            Self::CamelCase => {
+                let pascal = Self::PascalCase.apply_to_field(field);
+                let mut chars = pascal.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_ascii_lowercase().to_string() + chars.as_str()
+                })
+            }
+            Self::KebabCase => field.replace('_', "-"),
+            Self::ScreamingKebabCase => Self::ScreamingSnakeCase
+                .apply_to_field(field)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Apply the rule to a `PascalCase` variant name, the same way serde does.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::PascalCase => variant.to_owned(),
+            Self::LowerCase => variant.to_ascii_lowercase(),
+            Self::UpperCase => variant.to_ascii_uppercase(),
+            Self::CamelCase => {
+                let mut chars = variant.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_ascii_lowercase().to_string() + chars.as_str()
+                })
+            }
+            Self::SnakeCase => {
+                let mut snake = String::new();
+                for (idx, ch) in variant.char_indices() {
+                    if idx > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            Self::ScreamingSnakeCase => Self::SnakeCase
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            Self::KebabCase => {
+                Self::SnakeCase.apply_to_variant(variant).replace('_', "-")
+            }
+            Self::ScreamingKebabCase => Self::ScreamingSnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+}
+
+/// Find `serde(<key> = "...")` or `serde(<key>(deserialize = "..."))` among
+/// forwarded attributes.
+///
+/// The `deserialize` name is used, as it is what clients send.
+/// Attributes that are not valid `Meta`s are left to serde to complain about.
+pub fn find_serde_name<'a>(
+    attrs: impl IntoIterator<Item = &'a Meta>,
+    key: &str,
+) -> Option<LitStr> {
+    find_serde_names(attrs, key).pop()
+}
+
+/// Find all `serde(<key> = "...")`, e.g. for the repeatable `alias`.
+pub fn find_serde_names<'a>(
+    attrs: impl IntoIterator<Item = &'a Meta>,
+    key: &str,
+) -> Vec<LitStr> {
+    attrs
+        .into_iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| nested(attr.require_list().ok()?))
+        .flatten()
+        .filter(|item| item.path().is_ident(key))
+        .filter_map(|item| match item {
+            Meta::NameValue(nv) => lit_str(&nv.value),
+            Meta::List(lst) => nested(&lst)?
+                .into_iter()
+                .filter(|item| item.path().is_ident("deserialize"))
+                .find_map(|item| lit_str(&item.require_name_value().ok()?.value)),
+            Meta::Path(_) => None,
+        })
+        .collect()
+}
+
+fn nested(lst: &syn::MetaList) -> Option<Punctuated<Meta, Token![,]>> {
+    lst.parse_args_with(Punctuated::parse_terminated).ok()
+}
+
+fn lit_str(expr: &Expr) -> Option<LitStr> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(s), ..
+        }) => Some(s.clone()),
+        _ => None,
+    }
+}
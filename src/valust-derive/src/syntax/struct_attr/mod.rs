@@ -1,18 +1,28 @@
 use proc_macro2::TokenStream;
 use syn::{Attribute, Expr, Ident, LitStr, Meta, Path, Type};
 
+use super::serde::{RenameRule, find_serde_name};
+
 mod forward_attr;
 mod forward_derive;
 mod post;
 mod pre;
+mod pre_trans;
+mod presence;
 mod rename;
+mod valust;
+
+pub use valust::StructOptions;
 
 pub struct StructAttr {
     pub rename: Option<Ident>,
     pub forward_derive: Vec<Path>,
     pub pre: Vec<(Expr, Option<LitStr>)>,
+    pub pre_trans: Vec<(Expr, Option<LitStr>)>,
     pub post: Vec<(Expr, Option<LitStr>)>,
     pub forward_attr: Vec<Meta>,
+    pub options: StructOptions,
+    pub rename_all: Option<RenameRule>,
 }
 
 impl StructAttr {
@@ -22,8 +32,10 @@ impl StructAttr {
         let mut rename: Option<Ident> = None;
         let mut forward_derive: Vec<Path> = vec![];
         let mut pre: Vec<(Expr, Option<LitStr>)> = vec![];
+        let mut pre_trans: Vec<(Expr, Option<LitStr>)> = vec![];
         let mut post: Vec<(Expr, Option<LitStr>)> = vec![];
         let mut forward_attr: Vec<Meta> = vec![];
+        let mut options = StructOptions::default();
 
         for attr in attrs {
             if attr.path().is_ident("rename") {
@@ -38,6 +50,10 @@ impl StructAttr {
                 pre::parse_pre(&attr.meta, &mut pre)?;
             }
 
+            if attr.path().is_ident("pre_trans") {
+                pre_trans::parse_pre_trans(&attr.meta, &mut pre_trans)?;
+            }
+
             if attr.path().is_ident("post") {
                 post::parse_post(&attr.meta, &mut post)?;
             }
@@ -45,17 +61,40 @@ impl StructAttr {
             if attr.path().is_ident("forward_attr") {
                 forward_attr::parse_forward_attr(&attr.meta, &mut forward_attr)?;
             }
+
+            if attr.path().is_ident("valust") {
+                valust::parse_valust(&attr.meta, &mut options)?;
+            }
         }
 
+        let rename_all = find_serde_name(&forward_attr, "rename_all")
+            .map(|rule| RenameRule::parse(&rule))
+            .transpose()?;
+
         Ok(Self {
             rename,
             forward_derive,
             pre,
+            pre_trans,
             post,
             forward_attr,
+            options,
+            rename_all,
         })
     }
 
+    /// Whether the raw structure forwards a serde derive or attribute.
+    pub fn uses_serde(&self) -> bool {
+        self.forward_derive.iter().any(|path| {
+            path.segments.last().is_some_and(|seg| {
+                seg.ident == "Serialize" || seg.ident == "Deserialize"
+            })
+        }) || self
+            .forward_attr
+            .iter()
+            .any(|meta| meta.path().is_ident("serde"))
+    }
+
     pub fn gen_pre_expr(
         &self,
         name: &Ident,
@@ -64,6 +103,14 @@ impl StructAttr {
         pre::gen_pre_expr(self.pre.iter(), name, fields)
     }
 
+    pub fn gen_pre_trans_expr(
+        &self,
+        name: &Ident,
+        fields: impl Iterator<Item = (Ident, Type)>,
+    ) -> (Ident, TokenStream) {
+        pre_trans::gen_pre_trans_expr(self.pre_trans.iter(), name, fields)
+    }
+
     pub fn gen_post_expr<'a>(
         &self,
         name: &Ident,
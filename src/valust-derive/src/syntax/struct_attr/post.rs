@@ -56,6 +56,7 @@ pub fn gen_post_expr<'a, 'b>(
             &err_ident,
             msg.as_ref().map(|m| m.value()),
             expr,
+            None,
         );
         exprs.extend(quote! {
             if !(#expr) {
@@ -67,10 +68,12 @@ pub fn gen_post_expr<'a, 'b>(
 
     let fn_name = format_ident!("valust_post_{}", name, span = name.span());
     let fields = fields.into_iter().map(|(i, ty)| quote! { #i: &#ty });
+    // the fields are taken by reference, whatever their types
     let decl = quote! {
-        fn #fn_name (#(#fields),*, #err_ident: &mut ::valust::error::ValidationError) -> ::std::option::Option<()> {
+        #[allow(clippy::ptr_arg)]
+        fn #fn_name (#(#fields),*, #err_ident: &mut ::valust::error::ValidationError) -> ::core::option::Option<()> {
             #exprs
-            ::std::option::Option::Some(())
+            ::core::option::Option::Some(())
         }
     };
 
@@ -25,7 +25,7 @@ fn parse_item(buf: ParseStream) -> syn::Result<(Expr, Option<LitStr>)> {
     }
 }
 
-fn parse_items(
+pub(super) fn parse_items(
     buf: ParseStream,
 ) -> syn::Result<Punctuated<(Expr, Option<LitStr>), Token![,]>> {
     Punctuated::<_, Token![,]>::parse_terminated_with(buf, parse_item)
@@ -56,6 +56,7 @@ pub fn gen_pre_expr<'a>(
             &err_ident,
             msg.as_ref().map(|m| m.value()),
             expr,
+            None,
         );
         exprs.extend(quote! {
             if !(#expr) {
@@ -67,10 +68,12 @@ pub fn gen_pre_expr<'a>(
 
     let fn_name = format_ident!("valust_pre_{}", name, span = name.span());
     let fields = fields.into_iter().map(|(i, ty)| quote! { #i: &#ty });
+    // the fields are taken by reference, whatever their types
     let decl = quote! {
-        fn #fn_name (#(#fields),*, #err_ident: &mut ::valust::error::ValidationError) -> ::std::option::Option<()> {
+        #[allow(clippy::ptr_arg)]
+        fn #fn_name (#(#fields),*, #err_ident: &mut ::valust::error::ValidationError) -> ::core::option::Option<()> {
             #exprs
-            ::std::option::Option::Some(())
+            ::core::option::Option::Some(())
         }
     };
 
@@ -0,0 +1,62 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, LitStr, Meta, Type};
+
+use super::pre::parse_items;
+use crate::utils::create_error::create_meta_validate_error;
+
+pub fn parse_pre_trans(
+    meta: &Meta,
+    pre_trans: &mut Vec<(Expr, Option<LitStr>)>,
+) -> syn::Result<()> {
+    let lst = meta.require_list()?;
+    let args: Punctuated<(Expr, Option<LitStr>), _> =
+        lst.parse_args_with(parse_items)?;
+    pre_trans.extend(args);
+
+    Ok(())
+}
+
+/// Generate a function taking raw fields mutably.
+///
+/// Each expression may evaluate to `()`, `bool` or `Result<(), E>`.
+pub fn gen_pre_trans_expr<'a>(
+    pre_trans: impl Iterator<Item = &'a (Expr, Option<LitStr>)>,
+    name: &Ident,
+    fields: impl Iterator<Item = (Ident, Type)>,
+) -> (Ident, TokenStream) {
+    let err_ident = format_ident!("valust_pre_trans_err_{}", name, span = name.span());
+    let cause = format_ident!("valust_pre_trans_cause");
+
+    let mut exprs = TokenStream::new();
+    for (expr, msg) in pre_trans {
+        let invalid_err = create_meta_validate_error(
+            &err_ident,
+            msg.as_ref().map(|m| m.value()),
+            expr,
+            Some(&cause),
+        );
+        exprs.extend(quote! {
+            if let ::core::result::Result::Err(#cause) =
+                ::valust::__private::PreTransOutput::into_result({ #expr })
+            {
+                #invalid_err;
+                return None;
+            }
+        });
+    }
+
+    let fn_name = format_ident!("valust_pre_trans_{}", name, span = name.span());
+    let fields = fields.into_iter().map(|(i, ty)| quote! { #i: &mut #ty });
+    // the fields are taken by reference, whatever their types
+    let decl = quote! {
+        #[allow(clippy::ptr_arg)]
+        fn #fn_name (#(#fields),*, #err_ident: &mut ::valust::error::ValidationError) -> ::core::option::Option<()> {
+            #exprs
+            ::core::option::Option::Some(())
+        }
+    };
+
+    (fn_name, decl)
+}
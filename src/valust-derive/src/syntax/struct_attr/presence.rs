@@ -0,0 +1,162 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::meta::ParseNestedMeta;
+use syn::punctuated::Punctuated;
+use syn::{Ident, Path, Token};
+
+use crate::syntax::field::{Field, FieldContext, FieldName};
+use crate::utils::create_error::create_presence_error;
+
+const PRESENCE_TOO_FEW_FIELDS: &str = "expect at least two fields";
+
+/// Declarative rules about which `Option` fields are present.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PresenceKind {
+    ExactlyOneOf,
+    AtLeastOneOf,
+    MutuallyExclusive,
+    AllOrNone,
+}
+
+impl PresenceKind {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        Some(if path.is_ident("exactly_one_of") {
+            Self::ExactlyOneOf
+        } else if path.is_ident("at_least_one_of") {
+            Self::AtLeastOneOf
+        } else if path.is_ident("mutually_exclusive") {
+            Self::MutuallyExclusive
+        } else if path.is_ident("all_or_none") {
+            Self::AllOrNone
+        } else {
+            return None;
+        })
+    }
+
+    fn ident(self) -> &'static str {
+        match self {
+            Self::ExactlyOneOf => "exactly_one_of",
+            Self::AtLeastOneOf => "at_least_one_of",
+            Self::MutuallyExclusive => "mutually_exclusive",
+            Self::AllOrNone => "all_or_none",
+        }
+    }
+
+    fn message(self, fields: &str) -> String {
+        match self {
+            Self::ExactlyOneOf => format!("exactly one of {} must be present", fields),
+            Self::AtLeastOneOf => format!("at least one of {} must be present", fields),
+            Self::MutuallyExclusive => {
+                format!("at most one of {} may be present", fields)
+            }
+            Self::AllOrNone => {
+                format!("either all or none of {} must be present", fields)
+            }
+        }
+    }
+}
+
+pub struct PresenceRule {
+    pub kind: PresenceKind,
+    pub fields: Vec<Ident>,
+}
+
+impl PresenceRule {
+    /// Parse the inner part of e.g. `exactly_one_of(a, b)`.
+    pub fn parse(kind: PresenceKind, meta: &ParseNestedMeta) -> syn::Result<Self> {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        let fields = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+        if fields.len() < 2 {
+            return Err(meta.error(PRESENCE_TOO_FEW_FIELDS));
+        }
+        Ok(Self {
+            kind,
+            fields: fields.into_iter().collect(),
+        })
+    }
+
+    /// Generate the check over raw fields.
+    ///
+    /// Errors are reported on the fields that need to change:
+    /// the present ones if too many are given, and the missing ones otherwise.
+    pub fn gen_check(
+        &self,
+        err: &Ident,
+        fields: &[(&Field, FieldContext)],
+    ) -> syn::Result<TokenStream> {
+        let involved = self
+            .fields
+            .iter()
+            .map(|ident| {
+                fields
+                    .iter()
+                    .find(|(f, _)| matches!(&f.name, FieldName::Named(n) if n == ident))
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            ident.span(),
+                            format!("cannot find field `{}` in this struct", ident),
+                        )
+                    })
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        // the labels are filled in at runtime, so that they can be translated
+        let message = self.kind.message("{fields}");
+        let contexts = involved.iter().map(|(_, ctx)| ctx).collect::<Vec<_>>();
+        let expression = format!(
+            "{}({})",
+            self.kind.ident(),
+            self.fields
+                .iter()
+                .map(|f| f.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let total = involved.len();
+        let violated = match self.kind {
+            PresenceKind::ExactlyOneOf => quote! { valust_count != 1 },
+            PresenceKind::AtLeastOneOf => quote! { valust_count == 0 },
+            PresenceKind::MutuallyExclusive => quote! { valust_count > 1 },
+            PresenceKind::AllOrNone => {
+                quote! { valust_count != 0 && valust_count != #total }
+            }
+        };
+
+        let err_ref = format_ident!("valust_presence_err");
+        let idents = involved.iter().map(|(f, _)| f.name.name());
+        let reports = involved.iter().enumerate().map(|(idx, (field, ctx))| {
+            let report = match self.kind {
+                PresenceKind::ExactlyOneOf => {
+                    quote! { valust_count == 0 || valust_present[#idx] }
+                }
+                PresenceKind::AtLeastOneOf => quote! { true },
+                PresenceKind::MutuallyExclusive => quote! { valust_present[#idx] },
+                PresenceKind::AllOrNone => quote! { !valust_present[#idx] },
+            };
+            let error = create_presence_error(
+                &err_ref,
+                message.clone(),
+                &expression,
+                &field.get_raw_type(),
+                ctx,
+                &contexts,
+            );
+            quote! {
+                if #report {
+                    #error;
+                }
+            }
+        });
+
+        Ok(quote! {{
+            let #err_ref = &mut #err;
+            let valust_present = [#(::core::option::Option::is_some(&#idents)),*];
+            let valust_count = valust_present.iter().filter(|p| **p).count();
+            if #violated {
+                #(#reports)*
+            }
+        }})
+    }
+}
@@ -0,0 +1,50 @@
+use syn::{Expr, LitInt, Meta};
+
+use super::presence::{PresenceKind, PresenceRule};
+use crate::syntax::capture::ValueCapture;
+
+const META_SYNTAX_ERR_VALUST: &str = "\
+    Invalid `valust` usage.\n\
+    For usages, refer to the crate's doc.";
+
+#[derive(Default)]
+pub struct StructOptions {
+    pub value: Option<ValueCapture>,
+    pub fail_fast: bool,
+    pub presence: Vec<PresenceRule>,
+    pub pre_validate_with: Vec<Expr>,
+    pub validate_with: Vec<Expr>,
+    pub max_depth: Option<usize>,
+}
+
+pub fn parse_valust(meta: &Meta, options: &mut StructOptions) -> syn::Result<()> {
+    let lst = meta.require_list()?;
+    lst.parse_nested_meta(|meta| {
+        if meta.path.is_ident("value") {
+            ValueCapture::parse_option(meta, &mut options.value)
+        } else if meta.path.is_ident("fail_fast") {
+            if options.fail_fast {
+                return Err(meta.error("found multiple `fail_fast` options"));
+            }
+            options.fail_fast = true;
+            Ok(())
+        } else if let Some(kind) = PresenceKind::from_path(&meta.path) {
+            options.presence.push(PresenceRule::parse(kind, &meta)?);
+            Ok(())
+        } else if meta.path.is_ident("pre_validate_with") {
+            options.pre_validate_with.push(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("validate_with") {
+            options.validate_with.push(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("max_depth") {
+            if options.max_depth.is_some() {
+                return Err(meta.error("found multiple `max_depth` options"));
+            }
+            options.max_depth = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            Ok(())
+        } else {
+            Err(meta.error(META_SYNTAX_ERR_VALUST))
+        }
+    })
+}
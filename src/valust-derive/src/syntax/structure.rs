@@ -69,11 +69,17 @@ impl Structure {
         let raw_name = self.attrs.rename.clone().unwrap_or_else(|| {
             format_ident!("Raw{}", self.name, span = self.name.span())
         });
+        // computed and skipped fields are not part of the raw data
+        let raw_fields = self.fields.iter().filter(|f| f.is_raw());
         let raw_decl = {
-            let decls = self.fields.iter().map(|t| {
+            let decls = raw_fields.clone().map(|t| {
                 let ty = t.get_raw_type();
                 let vis = &t.vis;
-                let attr = t.operations.iter().flat_map(|op| op.gen_raw_attr(&t.name));
+                let ctx = t.context(&self.attrs);
+                let attr = t
+                    .operations
+                    .iter()
+                    .flat_map(|op| op.gen_raw_attr(&t.name, &ctx));
                 if self.is_named {
                     let name = t.name.name();
                     quote! {
@@ -101,7 +107,15 @@ impl Structure {
             }
         };
 
-        let packing_names = names.clone().map(|name| name.name());
+        let packing_names = raw_fields.clone().map(|f| f.name.name());
+        // raw fields are only modified by `pre_trans`
+        let packing_names = packing_names.map(|name| {
+            if self.attrs.pre_trans.is_empty() {
+                quote! { #name }
+            } else {
+                quote! { mut #name }
+            }
+        });
         let unpack_raw = if self.is_named {
             quote! { let #raw_name{ #(#packing_names,)* } = raw; }
         } else {
@@ -110,18 +124,44 @@ impl Structure {
 
         let err_ident =
             format_ident!("valust_impl_err_{}", self.name, span = self.name.span());
-        let err_init = quote! {
-            let mut #err_ident = ::valust::error::ValidationError::new();
+        // each stage starts with a fresh error, only mutable if it reports any
+        let err_init = |mutable: bool| {
+            let mutability = mutable.then(|| quote! { mut });
+            quote! {
+                let #mutability #err_ident = ::valust::error::ValidationError::new();
+            }
         };
 
-        let (pre, post) = {
-            let fields = self.fields.iter().map(|f| f.name.name());
+        let (pre_trans, pre, post) = {
+            let fields = raw_fields.clone().map(|f| f.name.name());
+
+            let pre_trans = if !self.attrs.pre_trans.is_empty() {
+                let (pre_trans, pre_trans_func) = self.attrs.gen_pre_trans_expr(
+                    &self.name,
+                    raw_fields
+                        .clone()
+                        .map(|field| (field.name.name(), field.get_raw_type())),
+                );
+                let pre_trans_fields = fields.clone();
+                let err_init = err_init(
+                    !self.attrs.pre.is_empty()
+                        || !self.attrs.options.presence.is_empty(),
+                );
+                quote! {
+                    #pre_trans_func
+                    #pre_trans(#(&mut #pre_trans_fields),*, &mut #err_ident);
+                    #err_ident.check()?;
+                    #err_init
+                }
+            } else {
+                quote! {}
+            };
 
             let pre = if !self.attrs.pre.is_empty() {
                 let (pre, pre_func) = self.attrs.gen_pre_expr(
                     &self.name,
-                    self.fields
-                        .iter()
+                    raw_fields
+                        .clone()
                         .map(|field| (field.name.name(), field.get_raw_type())),
                 );
                 let pre_fields = fields.clone();
@@ -140,7 +180,7 @@ impl Structure {
                         .iter()
                         .map(|field| (field.name.name(), &field.ty)),
                 );
-                let post_fields = fields;
+                let post_fields = self.fields.iter().map(|f| f.name.name());
                 quote! {
                     #post_func
                     #post(#(&#post_fields),*, &mut #err_ident);
@@ -148,19 +188,98 @@ impl Structure {
             } else {
                 quote! {}
             };
-            (pre, post)
+            (pre_trans, pre, post)
+        };
+
+        let pre_hooks = self.attrs.options.pre_validate_with.iter().map(|hook| {
+            quote! { (#hook)(&raw, &mut #err_ident); }
+        });
+        let post_hooks = self.attrs.options.validate_with.iter().map(|hook| {
+            quote! { (#hook)(&valust_validated, &mut #err_ident); }
+        });
+
+        let presence = {
+            let contexts = raw_fields
+                .clone()
+                .map(|f| (f, f.context(&self.attrs)))
+                .collect::<Vec<_>>();
+            let checks = self
+                .attrs
+                .options
+                .presence
+                .iter()
+                .filter_map(|rule| match rule.gen_check(&err_ident, &contexts) {
+                    Ok(check) => Some(check),
+                    Err(err) => {
+                        error.push(err);
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+            quote! { #(#checks)* }
         };
 
-        let field_validate = self
+        let depth_check = self.attrs.options.max_depth.map(|max| {
+            let message = format!("nesting depth exceeds the maximum of {}", max);
+            let expression = format!("max_depth = {}", max);
+            let type_name = self.name.to_string();
+            quote! {
+                if valust_depth > #max {
+                    ::valust::__private::depth_error(&mut #err_ident, #message, #expression, #type_name);
+                    return Err(#err_ident);
+                }
+            }
+        });
+
+        // forwarded fields may have references as well, as told by their types
+        let has_refs = self
             .fields
             .iter()
+            .filter_map(|f| f.gen_has_refs(&self.name))
+            .collect::<Vec<_>>();
+        let (has_refs_const, collect_refs_fn, check_refs) = if has_refs.is_empty() {
+            (quote! {}, quote! {}, quote! {})
+        } else {
+            let collect_refs = self
+                .fields
+                .iter()
+                .map(|f| f.gen_collect_refs(&f.context(&self.attrs)))
+                .filter(|code| !code.is_empty());
+            (
+                quote! {
+                    const HAS_REFS: bool = #(#has_refs)||*;
+                },
+                quote! {
+                    fn collect_refs(&self, valust_path: &str, valust_collection: &str, valust_refs: &mut ::valust::refs::RefCollector) {
+                        #(#collect_refs)*
+                    }
+                },
+                // only the root value checks the references, so that errors
+                // have full paths
+                quote! { .and_then(::valust::refs::check_root) },
+            )
+        };
+
+        let fail_fast = self.attrs.options.fail_fast;
+        // fields without any operation are moved as is
+        let checked = self.fields.iter().filter(|f| !f.operations.is_empty());
+        let field_validate = checked
+            .clone()
             .map(|field| -> syn::Result<TokenStream> {
                 let ident = field.name.name();
-                let out_ty = &field.ty;
-                let (func_name, func_body) = field.gen_validate_func()?;
+                let ctx = field.context(&self.attrs);
+                let (func_name, func_body) = field.gen_validate_func(&ctx)?;
+                let stop = if fail_fast {
+                    quote! { #ident.is_none() || #err_ident.is_full() }
+                } else {
+                    quote! { #err_ident.is_full() }
+                };
                 Ok(quote! {
                     #func_body
-                    let #ident: Option<#out_ty> = #func_name(#ident, &mut #err_ident);
+                    let #ident = #func_name(#ident, &mut #err_ident, valust_depth);
+                    if #stop {
+                        return Err(#err_ident);
+                    }
                 })
             })
             .filter_map(|field| match field {
@@ -172,17 +291,21 @@ impl Structure {
             });
 
         let unwrap_validated = {
-            let check = names.clone().map(|name| {
-                let text = format!(
-                    "Unexpected error occurred in processing field `{}`",
-                    name.struct_key()
-                );
-                let name = name.name();
-                quote! { let #name = #name.expect(#text); }
-            });
-            quote! { #(#check)* }
+            let checked = checked.map(|f| f.name.name()).collect::<Vec<_>>();
+            if checked.is_empty() {
+                quote! {}
+            } else {
+                quote! {
+                    let (#(::core::option::Option::Some(#checked),)*) = (#(#checked,)*) else {
+                        unreachable!("no error reported for a failed field")
+                    };
+                }
+            }
         };
 
+        // computed fields see all the validated fields
+        let derived = self.fields.iter().filter_map(Field::gen_derived);
+
         let packing_names = names.clone().map(|name| name.name());
         let pack_raw = if self.is_named {
             quote! { #ty{ #(#packing_names,)* } }
@@ -190,6 +313,29 @@ impl Structure {
             quote! { #ty(#(#packing_names),*) }
         };
 
+        let options = &self.attrs.options;
+        let init_pre = err_init(
+            options.max_depth.is_some()
+                || !options.pre_validate_with.is_empty()
+                || !self.attrs.pre_trans.is_empty()
+                || !self.attrs.pre.is_empty()
+                || !options.presence.is_empty(),
+        );
+        let init_fields =
+            err_init(self.fields.iter().any(|f| !f.operations.is_empty()));
+        let init_post = err_init(!self.attrs.post.is_empty());
+        // checks of the validated structure, skipped if there are none
+        let validated_checks = if options.validate_with.is_empty() {
+            quote! {}
+        } else {
+            let err_init = err_init(true);
+            quote! {
+                #err_init
+                #(#post_hooks)*
+                #err_ident.check()?;
+            }
+        };
+
         let expanded = quote! {
             #[automatically_derived]
             #raw_decl
@@ -199,25 +345,44 @@ impl Structure {
             impl ::valust::Validate for #ty {
                 type Raw = #raw_name;
 
+                #has_refs_const
+
                 fn validate(raw: Self::Raw) -> Result<Self, ::valust::error::ValidationError> {
-                    #unpack_raw
+                    Self::validate_nested(raw, 0)#check_refs
+                }
 
-                    #err_init
+                fn validate_nested(raw: Self::Raw, valust_depth: usize) -> Result<Self, ::valust::error::ValidationError> {
+                    // for the length probes of captured values, if any
+                    #[allow(unused_imports)]
+                    use ::valust::__private::{KnownLen as _, UnknownLen as _};
+
+                    #init_pre
+                    #depth_check
+                    #(#pre_hooks)*
+                    #unpack_raw
+                    #pre_trans
                     #pre
+                    #presence
                     #err_ident.check()?;
 
-                    #err_init
+                    #init_fields
                     #(#field_validate)*
                     #err_ident.check()?;
 
                     #unwrap_validated
+                    #(#derived)*
 
-                    #err_init
+                    #init_post
                     #post
                     #err_ident.check()?;
 
-                    Ok(#pack_raw)
+                    let valust_validated = #pack_raw;
+                    #validated_checks
+
+                    Ok(valust_validated)
                 }
+
+                #collect_refs_fn
             }
         };
         error.check()?;
@@ -0,0 +1,64 @@
+use proc_macro2::Span;
+use syn::{Expr, ExprLit, Lit};
+
+/// Compile a string literal regex, so that typos are reported at compile
+/// time.
+///
+/// Returns the named groups of the regex, or `None` if it is not a literal or
+/// not compiled.
+pub fn check_regex_lit(regex: &Expr) -> syn::Result<Option<Vec<String>>> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Str(lit), ..
+    }) = regex
+    else {
+        return Ok(None);
+    };
+    check_regex(&lit.value(), lit.span())
+}
+
+/// Compile a regex known during macro expansion, reporting errors at `span`.
+///
+/// The regex is compiled with the engine selected at runtime, and is not
+/// checked if no regex feature is enabled.
+pub fn check_regex(regex: &str, span: Span) -> syn::Result<Option<Vec<String>>> {
+    match compile(regex) {
+        Some(Err(e)) => Err(syn::Error::new(span, format!("invalid regex: {}", e))),
+        Some(Ok(groups)) => Ok(Some(groups)),
+        None => Ok(None),
+    }
+}
+
+#[cfg(feature = "fancy-regex")]
+fn compile(regex: &str) -> Option<Result<Vec<String>, String>> {
+    Some(
+        ::fancy_regex::Regex::new(regex)
+            .map(|re| re.capture_names().flatten().map(String::from).collect())
+            .map_err(|e| e.to_string()),
+    )
+}
+
+#[cfg(all(feature = "regex", not(feature = "fancy-regex")))]
+fn compile(regex: &str) -> Option<Result<Vec<String>, String>> {
+    Some(
+        ::regex::Regex::new(regex)
+            .map(|re| re.capture_names().flatten().map(String::from).collect())
+            .map_err(|e| e.to_string()),
+    )
+}
+
+#[cfg(all(
+    feature = "regex-lite",
+    not(any(feature = "regex", feature = "fancy-regex"))
+))]
+fn compile(regex: &str) -> Option<Result<Vec<String>, String>> {
+    Some(
+        ::regex_lite::Regex::new(regex)
+            .map(|re| re.capture_names().flatten().map(String::from).collect())
+            .map_err(|e| e.to_string()),
+    )
+}
+
+#[cfg(not(any(feature = "regex", feature = "regex-lite", feature = "fancy-regex")))]
+fn compile(_regex: &str) -> Option<Result<Vec<String>, String>> {
+    None
+}
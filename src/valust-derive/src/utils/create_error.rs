@@ -2,13 +2,14 @@ use proc_macro2::{Ident, TokenStream};
 use quote::{ToTokens, format_ident, quote};
 use syn::Type;
 
-use crate::syntax::field::FieldName;
+use crate::syntax::field::{FieldContext, FieldName};
 
 /// Output
 ///
 /// ```rust,ignore
-/// error.push_validate_error(ValidateError { .. })
+/// ::valust::__private::validate_error(&mut error, &ValidateSite { .. }, ..)
 /// ```
+#[allow(clippy::too_many_arguments)]
 pub fn create_validate_error(
     error_ident: &Ident,
     field: &FieldName,
@@ -16,37 +17,41 @@ pub fn create_validate_error(
     message: Option<String>,
     expr: impl AsRef<str>,
     ty: &Type,
-    display: bool,
+    ctx: &FieldContext,
+    params: &[(&str, TokenStream)],
 ) -> TokenStream {
-    let field_text = field.struct_key().to_string();
+    let field_text = &ctx.key;
+    let label = ctx.label();
     let field = field.name();
     let cause = cause
-        .map(|cause| quote! { ::std::option::Option::Some(::std::boxed::Box::new(#cause)) })
-        .unwrap_or(quote! { ::std::option::Option::None });
+        .map(|cause| quote! { ::core::option::Option::Some(::valust::__private::Box::new(#cause)) })
+        .unwrap_or(quote! { ::core::option::Option::None });
     let message = message
-        .map(|m| quote! { ::std::option::Option::Some(#m) })
-        .unwrap_or(quote! { ::std::option::Option::None });
+        .map(|m| quote! { ::core::option::Option::Some(#m) })
+        .unwrap_or(quote! { ::core::option::Option::None });
     let expr_text = expr.as_ref();
     let type_text = ty.to_token_stream().to_string();
-    let value_format = if display {
-        let value_formatter = format!("({}) {{:?}}", type_text);
-        quote! { format!(#value_formatter, #field) }
-    } else {
-        let value_formatter = format!("({})", type_text);
-        quote! { format!(#value_formatter)}
-    };
+    let (value, len) = ctx.value.gen_value(&field);
+    let limits = ctx.value.gen_limits();
+    let params = params.iter().map(|(name, value)| {
+        quote! { (#name, &(#value) as &dyn ::core::fmt::Debug) }
+    });
 
     quote! {
-        #error_ident.push_validate_error(
-            ::valust::error::validate::ValidateError {
+        ::valust::__private::validate_error(
+            #error_ident,
+            &::valust::__private::ValidateSite {
                 field: #field_text,
-                path: format!("{}", #field_text),
-                value: #value_format,
-                cause: #cause,
+                label: #label,
                 message: #message,
                 expression: #expr_text,
                 type_name: #type_text,
-            }
+                limits: #limits,
+            },
+            #value,
+            #len,
+            #cause,
+            &[#(#params),*],
         )
     }
 }
@@ -54,52 +59,161 @@ pub fn create_validate_error(
 /// Output
 ///
 /// ```rust,ignore
-/// error.push_validate_error(ValidateError { .. })
+/// ::valust::__private::violation_error(&mut error, &ValidateSite { .. }, .., violation)
+/// ```
+pub fn create_violation_error(
+    error_ident: &Ident,
+    field: &FieldName,
+    violation: &Ident,
+    expr: impl AsRef<str>,
+    ty: &Type,
+    ctx: &FieldContext,
+) -> TokenStream {
+    let field_text = &ctx.key;
+    let label = ctx.label();
+    // the violation's message refers to the label with `{label}`
+    let slot = ctx.label_slot();
+    let field = field.name();
+    let expr_text = expr.as_ref();
+    let type_text = ty.to_token_stream().to_string();
+    let (value, len) = ctx.value.gen_value(&field);
+    let limits = ctx.value.gen_limits();
+
+    quote! {
+        ::valust::__private::violation_error(
+            #error_ident,
+            &::valust::__private::ValidateSite {
+                field: #field_text,
+                label: #label,
+                message: ::core::option::Option::Some(#slot),
+                expression: #expr_text,
+                type_name: #type_text,
+                limits: #limits,
+            },
+            #value,
+            #len,
+            #violation,
+        )
+    }
+}
+
+/// Output
+///
+/// ```rust,ignore
+/// ::valust::__private::presence_error(&mut error, &ValidateSite { .. }, &[(field, label, slot), ..])
+/// ```
+///
+/// `message` refers to the labels of all the `fields` of the rule with
+/// `{fields}`.
+pub fn create_presence_error(
+    error_ident: &Ident,
+    message: String,
+    expr: impl AsRef<str>,
+    ty: &Type,
+    ctx: &FieldContext,
+    fields: &[&FieldContext],
+) -> TokenStream {
+    let field_text = &ctx.key;
+    let label = ctx.label();
+    let expr_text = expr.as_ref();
+    let type_text = ty.to_token_stream().to_string();
+    let fields = fields.iter().map(|f| {
+        let (key, label, slot) = (&f.key, f.label(), f.label_slot());
+        quote! { (#key, #label, #slot) }
+    });
+
+    quote! {
+        ::valust::__private::presence_error(
+            #error_ident,
+            &::valust::__private::ValidateSite {
+                field: #field_text,
+                label: #label,
+                message: ::core::option::Option::Some(#message),
+                expression: #expr_text,
+                type_name: #type_text,
+                limits: ::valust::error::value::ValueLimits::UNLIMITED,
+            },
+            &[#(#fields),*],
+        )
+    }
+}
+
+/// Output
+///
+/// ```rust,ignore
+/// ::valust::__private::meta_error(&mut error, ..)
 /// ```
 pub fn create_meta_validate_error(
     error_ident: &Ident,
     message: Option<String>,
     expr: impl ToTokens,
+    cause: Option<&Ident>,
 ) -> TokenStream {
+    // `cause` is already an `Option<Box<dyn ErrorShow>>`
+    let cause = cause
+        .map(|cause| quote! { #cause })
+        .unwrap_or(quote! { ::core::option::Option::None });
     let message = message
-        .map(|m| quote! { ::std::option::Option::Some(#m) })
-        .unwrap_or(quote! { ::std::option::Option::None });
+        .map(|m| quote! { ::core::option::Option::Some(#m) })
+        .unwrap_or(quote! { ::core::option::Option::None });
     let expr_text = expr.to_token_stream().to_string();
 
     quote! {
-        #error_ident.push_validate_error(
-            ::valust::error::validate::ValidateError {
-                field: "<meta>",
-                path: format!("<meta>"),
-                value: format!("<meta>"),
-                cause: ::std::option::Option::None,
-                message: #message,
-                expression: #expr_text,
-                type_name: "<meta>",
-            }
-        )
+        ::valust::__private::meta_error(#error_ident, #message, #expr_text, #cause)
     }
 }
 
 /// Output
 ///
 /// ```rust,ignore
-/// error.push_validate_error(TransformError { .. })
+/// let valust_snapshot_x = ::valust::__private::snapshot(.., ..);
+/// ```
+///
+/// Captures `value` before a fallible transformer consumes it, so that no
+/// clone is needed. `None` if the value is not captured.
+pub fn create_snapshot(
+    value: &Ident,
+    field: &FieldName,
+    ctx: &FieldContext,
+) -> Option<(Ident, TokenStream)> {
+    if !ctx.value.needs_value() {
+        return None;
+    }
+    let field = field.name();
+    let snapshot = format_ident!("valust_snapshot_{}", field, span = field.span());
+    let (value, len) = ctx.value.gen_value(value);
+    let limits = ctx.value.gen_limits();
+    let expanded = quote! {
+        let #snapshot = ::valust::__private::snapshot(#value, #limits, #len);
+    };
+    Some((snapshot, expanded))
+}
+
+fn gen_snapshot_arg(snapshot: Option<&Ident>) -> TokenStream {
+    snapshot
+        .map(|s| quote! { ::core::option::Option::as_ref(&#s) })
+        .unwrap_or(quote! { ::core::option::Option::None })
+}
+
+/// Output
+///
+/// ```rust,ignore
+/// ::valust::__private::transform_error(&mut error, &TransformSite { .. }, .., Box::new(cause))
 /// ```
 pub fn create_transform_error(
     error_ident: &Ident,
-    field: &FieldName,
     cause: &Ident,
     message: Option<String>,
     expr: impl ToTokens,
     (origin_ty, out_ty): (Option<&Type>, Option<&Type>),
-    display: bool,
-) -> (Option<Ident>, TokenStream) {
-    let field_text = field.struct_key().to_string();
-    let field = field.name();
+    snapshot: Option<&Ident>,
+    ctx: &FieldContext,
+) -> TokenStream {
+    let field_text = &ctx.key;
+    let label = ctx.label();
     let message = message
-        .map(|m| quote! { ::std::option::Option::Some(#m) })
-        .unwrap_or(quote! { ::std::option::Option::None });
+        .map(|m| quote! { ::core::option::Option::Some(#m) })
+        .unwrap_or(quote! { ::core::option::Option::None });
     let expr_text = expr.to_token_stream().to_string();
     let orig_type_text = if let Some(origin_ty) = origin_ty {
         origin_ty.to_token_stream().to_string()
@@ -111,32 +225,111 @@ pub fn create_transform_error(
     } else {
         "<unknown>".to_string()
     };
-    let (ident_clone, value_format) = if display {
-        let value_formatter = format!("({}) {{:?}}", orig_type_text);
-        let ident_clone =
-            format_ident!("valust_format_err_clone_{}", field, span = field.span());
-        (
-            Some(ident_clone.clone()),
-            quote! { format!(#value_formatter, #ident_clone) },
+    let value = gen_snapshot_arg(snapshot);
+    let limits = ctx.value.gen_limits();
+
+    quote! {
+        ::valust::__private::transform_error(
+            #error_ident,
+            &::valust::__private::TransformSite {
+                field: #field_text,
+                label: #label,
+                message: #message,
+                expression: #expr_text,
+                source_type_name: #orig_type_text,
+                target_type_name: #out_type_text,
+                limits: #limits,
+            },
+            #value,
+            ::valust::__private::Box::new(#cause),
+        )
+    }
+}
+
+/// Output
+///
+/// ```rust,ignore
+/// ::valust::__private::transformer_error(&mut error, &TransformerSite { .. }, .., failure)
+/// ```
+pub fn create_transformer_error(
+    error_ident: &Ident,
+    failure: &Ident,
+    message: Option<String>,
+    expr: impl ToTokens,
+    snapshot: Option<&Ident>,
+    ctx: &FieldContext,
+) -> TokenStream {
+    let field_text = &ctx.key;
+    let label = ctx.label();
+    let message = message
+        .map(|m| quote! { ::core::option::Option::Some(#m) })
+        .unwrap_or(quote! { ::core::option::Option::None });
+    let expr_text = format!("with({})", expr.to_token_stream());
+    let value = gen_snapshot_arg(snapshot);
+    let limits = ctx.value.gen_limits();
+
+    quote! {
+        ::valust::__private::transformer_error(
+            #error_ident,
+            &::valust::__private::TransformerSite {
+                field: #field_text,
+                label: #label,
+                message: #message,
+                expression: #expr_text,
+                limits: #limits,
+            },
+            #value,
+            #failure,
         )
+    }
+}
+
+/// Output
+///
+/// ```rust,ignore
+/// ::valust::__private::captures_error(&mut error, &TransformSite { .. }, .., failure)
+/// ```
+///
+/// The field is only borrowed by `captures(...)`, so its value is taken from
+/// the field itself.
+pub fn create_captures_error(
+    error_ident: &Ident,
+    field: &FieldName,
+    failure: &Ident,
+    message: Option<String>,
+    expr_text: String,
+    out_ty: Option<&Type>,
+    ctx: &FieldContext,
+) -> TokenStream {
+    let field_text = &ctx.key;
+    let label = ctx.label();
+    let field = field.name();
+    let message = message
+        .map(|m| quote! { ::core::option::Option::Some(#m) })
+        .unwrap_or(quote! { ::core::option::Option::None });
+    let out_type_text = if let Some(out_ty) = out_ty {
+        out_ty.to_token_stream().to_string()
     } else {
-        let value_formatter = format!("({})", orig_type_text);
-        (None, quote! { format!(#value_formatter) })
+        "<unknown>".to_string()
     };
+    let (value, len) = ctx.value.gen_value(&field);
+    let limits = ctx.value.gen_limits();
 
-    let expanded = quote! {
-        #error_ident.push_transform_error(
-            ::valust::error::transform::TransformError {
+    quote! {
+        ::valust::__private::captures_error(
+            #error_ident,
+            &::valust::__private::TransformSite {
                 field: #field_text,
-                path: format!("{}", #field_text),
-                value: #value_format,
-                cause: ::std::boxed::Box::new(#cause),
+                label: #label,
                 message: #message,
                 expression: #expr_text,
-                source_type_name: #orig_type_text,
+                source_type_name: "String",
                 target_type_name: #out_type_text,
-            }
-        );
-    };
-    (ident_clone, expanded)
+                limits: #limits,
+            },
+            #value,
+            #len,
+            #failure,
+        )
+    }
 }
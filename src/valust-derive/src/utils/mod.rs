@@ -1,5 +1,8 @@
+pub mod check_regex;
 pub mod create_error;
 pub mod error;
+pub mod parse_kv;
 pub mod require_bool;
 pub mod require_lit_str;
 pub mod require_single;
+pub mod types;
@@ -0,0 +1,25 @@
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, Token};
+
+/// Parse `key` or `key = <expr>`.
+pub fn parse_kv(buf: ParseStream) -> syn::Result<(Ident, Option<Expr>)> {
+    let ident: Ident = buf.parse()?;
+    if buf.peek(Token![=]) {
+        buf.parse::<Token![=]>()?;
+        let expr: Expr = buf.parse()?;
+        return Ok((ident, Some(expr)));
+    }
+    Ok((ident, None))
+}
+
+/// Parse `(key, key = <expr>, ...)`.
+pub fn parse_kv_list(tt: ParseStream) -> syn::Result<Vec<(Ident, Option<Expr>)>> {
+    let content;
+    syn::parenthesized!(content in tt);
+    Ok(
+        Punctuated::<_, Token![,]>::parse_terminated_with(&content, parse_kv)?
+            .into_iter()
+            .collect(),
+    )
+}
@@ -0,0 +1,158 @@
+//! Regex captures parsed into structures.
+//!
+//! `#[trans(captures(<regex> => T))]` matches a string against a regex and
+//! builds a `T` from its named groups with [`FromCaptures`], which
+//! `#[derive(FromCaptures)]` implements by parsing each field from the group of
+//! the same name. Every group failing to parse is reported as its own
+//! [`TransformError`](crate::error::transform::TransformError).
+//!
+//! ```rust
+//! use valust::captures::{CaptureFailure, FromCaptures, parse_group};
+//!
+//! struct Version {
+//!     major: u32,
+//!     minor: u32,
+//! }
+//!
+//! impl FromCaptures for Version {
+//!     fn from_captures<'h>(
+//!         groups: &dyn Fn(&'static str) -> Option<&'h str>,
+//!         failures: &mut Vec<CaptureFailure>,
+//!     ) -> Option<Self> {
+//!         let major = parse_group(groups, "major", failures);
+//!         let minor = parse_group(groups, "minor", failures);
+//!         Some(Self { major: major?, minor: minor? })
+//!     }
+//! }
+//!
+//! let groups = |name: &'static str| match name {
+//!     "major" => Some("1"),
+//!     _ => Some("x"),
+//! };
+//! let mut failures = Vec::new();
+//! assert!(Version::from_captures(&groups, &mut failures).is_none());
+//! assert_eq!(failures.len(), 1);
+//! assert_eq!(failures[0].group, "minor");
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::error::ErrorShow;
+
+/// Types built from the named groups of a regex match.
+pub trait FromCaptures: Sized {
+    /// Names of the groups read by the type.
+    ///
+    /// String literal regexes of `trans(captures(...))` are checked at compile
+    /// time to have all of them, `#[derive(FromCaptures)]` lists every field.
+    const GROUPS: &'static [&'static str] = &[];
+
+    /// Build a value from the text of the groups, returned by `groups`.
+    ///
+    /// Every failing group is pushed to `failures`, and `None` is returned if
+    /// there is any.
+    fn from_captures<'h>(
+        groups: &dyn Fn(&'static str) -> Option<&'h str>,
+        failures: &mut Vec<CaptureFailure>,
+    ) -> Option<Self>;
+}
+
+/// A group which failed to parse.
+#[derive(Debug)]
+pub struct CaptureFailure {
+    /// Name of the group.
+    pub group: &'static str,
+    /// Text of the group, `None` if the group did not participate in the
+    /// match.
+    pub text: Option<String>,
+    /// Type the group was parsed into.
+    pub type_name: &'static str,
+    /// The underlying error.
+    pub cause: Box<dyn ErrorShow + 'static>,
+}
+
+/// Error of a required group which did not participate in the match.
+#[derive(Debug, Clone, Copy)]
+pub struct MissingGroup;
+
+impl fmt::Display for MissingGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the group did not participate in the match")
+    }
+}
+
+/// Error of a value not matching the regex of `captures(...)`.
+#[derive(Debug, Clone, Copy)]
+pub struct NoMatch;
+
+impl fmt::Display for NoMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the value does not match the regex")
+    }
+}
+
+/// Parse a required group with [`FromStr`].
+pub fn parse_group<'h, T>(
+    groups: &dyn Fn(&'static str) -> Option<&'h str>,
+    group: &'static str,
+    failures: &mut Vec<CaptureFailure>,
+) -> Option<T>
+where
+    T: FromStr,
+    T::Err: ErrorShow + 'static,
+{
+    let Some(text) = groups(group) else {
+        failures.push(CaptureFailure {
+            group,
+            text: None,
+            type_name: type_name::<T>(),
+            cause: Box::new(MissingGroup),
+        });
+        return None;
+    };
+    parse_text(text, group, failures)
+}
+
+/// Parse an optional group with [`FromStr`], which is `None` if the group did
+/// not participate in the match.
+pub fn parse_optional_group<'h, T>(
+    groups: &dyn Fn(&'static str) -> Option<&'h str>,
+    group: &'static str,
+    failures: &mut Vec<CaptureFailure>,
+) -> Option<Option<T>>
+where
+    T: FromStr,
+    T::Err: ErrorShow + 'static,
+{
+    match groups(group) {
+        Some(text) => parse_text(text, group, failures).map(Some),
+        None => Some(None),
+    }
+}
+
+fn parse_text<T>(
+    text: &str,
+    group: &'static str,
+    failures: &mut Vec<CaptureFailure>,
+) -> Option<T>
+where
+    T: FromStr,
+    T::Err: ErrorShow + 'static,
+{
+    text.parse()
+        .map_err(|cause| {
+            failures.push(CaptureFailure {
+                group,
+                text: Some(text.to_owned()),
+                type_name: type_name::<T>(),
+                cause: Box::new(cause),
+            })
+        })
+        .ok()
+}
@@ -1,6 +1,7 @@
 //! Display trait for error types.
 
-use std::fmt::{self, Write};
+use alloc::string::String;
+use core::fmt::{self, Write};
 
 use sealed::sealed;
 
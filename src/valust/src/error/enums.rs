@@ -0,0 +1,52 @@
+//! Errors of string-backed enums.
+
+use alloc::string::String;
+use core::fmt;
+
+/// Error of the `FromStr` implementation generated by `#[derive(ValustEnum)]`.
+///
+/// It carries the accepted names, so that transform errors tell what was
+/// expected.
+///
+/// ```rust
+/// use valust::error::enums::ParseEnumError;
+///
+/// let err = ParseEnumError {
+///     enum_name: "Status",
+///     input: "deleted".to_owned(),
+///     expected: &["draft", "published"],
+/// };
+/// assert_eq!(
+///     err.to_string(),
+///     "unknown `Status` variant `deleted`, expected one of `draft`, `published`"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnumError {
+    /// Name of the enum.
+    pub enum_name: &'static str,
+    /// The string that failed to parse.
+    pub input: String,
+    /// Accepted names, without aliases.
+    pub expected: &'static [&'static str],
+}
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown `{}` variant `{}`, expected one of ",
+            self.enum_name, self.input
+        )?;
+        for (idx, name) in self.expected.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}`", name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseEnumError {}
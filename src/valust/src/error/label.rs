@@ -0,0 +1,127 @@
+//! Human-friendly field labels.
+//!
+//! Every error carries a label, which is the field's `#[valust(label = "...")]`,
+//! or its external name if no label is given. Messages may refer to the label
+//! with a `{label}` placeholder; built-in default messages always do.
+//!
+//! Labels can be translated at runtime with [`set_label_hook`].
+
+use alloc::borrow::Cow;
+
+/// Hook translating labels.
+///
+/// The hook receives the external name of the field and its label, and returns
+/// the label to use, or `None` to keep the original one.
+pub type LabelHook = fn(field: &str, label: &str) -> Option<Cow<'static, str>>;
+
+#[cfg(feature = "std")]
+static LABEL_HOOK: std::sync::RwLock<Option<LabelHook>> = std::sync::RwLock::new(None);
+
+/// Set the hook used to translate labels, or `None` to remove it.
+///
+/// Without the `std` feature, the hook can only be set once: later calls are
+/// ignored.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use valust::error::label::{localize, set_label_hook};
+///
+/// fn french(field: &str, _label: &str) -> Option<Cow<'static, str>> {
+///     match field {
+///         "email" => Some("Adresse e-mail".into()),
+///         _ => None,
+///     }
+/// }
+///
+/// set_label_hook(Some(french));
+/// assert_eq!(localize("email", "E-mail address"), "Adresse e-mail");
+/// assert_eq!(localize("name", "Name"), "Name");
+/// # set_label_hook(None);
+/// ```
+pub fn set_label_hook(hook: Option<LabelHook>) {
+    #[cfg(feature = "std")]
+    {
+        // a panic cannot leave a `fn` pointer half-written
+        *LABEL_HOOK
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = hook;
+    }
+    #[cfg(not(feature = "std"))]
+    set_once::set(hook);
+}
+
+/// Get the hook used to translate labels.
+pub fn label_hook() -> Option<LabelHook> {
+    #[cfg(feature = "std")]
+    return *LABEL_HOOK
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    #[cfg(not(feature = "std"))]
+    return set_once::get();
+}
+
+/// Set-once storage of the hook, as `core` has no lock to guard a `fn`
+/// pointer with.
+#[cfg(not(feature = "std"))]
+mod set_once {
+    use core::cell::UnsafeCell;
+    use core::sync::atomic::{AtomicU8, Ordering};
+
+    use super::LabelHook;
+
+    const UNSET: u8 = 0;
+    const WRITING: u8 = 1;
+    const SET: u8 = 2;
+
+    struct Slot(UnsafeCell<Option<LabelHook>>);
+
+    // SAFETY: the hook is only written by the caller moving `STATE` from
+    // `UNSET` to `WRITING`, and only read once `STATE` is `SET`.
+    unsafe impl Sync for Slot {}
+
+    static STATE: AtomicU8 = AtomicU8::new(UNSET);
+    static HOOK: Slot = Slot(UnsafeCell::new(None));
+
+    pub fn set(hook: Option<LabelHook>) {
+        if STATE
+            .compare_exchange(UNSET, WRITING, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            // SAFETY: `STATE` is `WRITING`, so no other thread accesses the slot.
+            unsafe { *HOOK.0.get() = hook };
+            STATE.store(SET, Ordering::Release);
+        }
+    }
+
+    pub fn get() -> Option<LabelHook> {
+        if STATE.load(Ordering::Acquire) != SET {
+            return None;
+        }
+        // SAFETY: `STATE` is `SET`, so the slot is never written again.
+        unsafe { *HOOK.0.get() }
+    }
+}
+
+/// Translate a label with the hook, if any.
+pub fn localize(field: &str, label: &'static str) -> Cow<'static, str> {
+    label_hook()
+        .and_then(|hook| hook(field, label))
+        .unwrap_or(Cow::Borrowed(label))
+}
+
+/// Replace the `{label}` placeholders of a message.
+///
+/// ```rust
+/// use valust::error::label::render_message;
+///
+/// let msg = render_message("{label} is not a valid email address", "E-mail address");
+/// assert_eq!(msg, "E-mail address is not a valid email address");
+/// ```
+pub fn render_message(message: &'static str, label: &str) -> Cow<'static, str> {
+    if message.contains("{label}") {
+        Cow::Owned(message.replace("{label}", label))
+    } else {
+        Cow::Borrowed(message)
+    }
+}
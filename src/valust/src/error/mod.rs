@@ -1,10 +1,17 @@
 //! Human-readable error produced by `valust`.
 
 pub mod display;
+pub mod enums;
+pub mod label;
 pub mod transform;
 pub mod validate;
+pub mod value;
 
-use std::fmt::{self, Debug, Display, Write};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Write};
+use core::sync::atomic::{AtomicUsize, Ordering};
 
 use sealed::sealed;
 use transform::TransformError;
@@ -18,6 +25,33 @@ pub trait ErrorShow: Debug + Display {}
 
 impl<T: Debug + Display> ErrorShow for T {}
 
+static MAX_ERRORS: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Set the maximum number of errors collected in one error set.
+///
+/// Once the limit is reached, further errors are dropped and the set is marked
+/// as [`truncated`](ValidationError::truncated). The macro-generated code also
+/// stops validating the remaining fields, which protects against hostile
+/// payloads producing huge error sets.
+///
+/// `None` means "no limit", which is the default.
+///
+/// ```rust
+/// use valust::error::{max_errors, set_max_errors};
+///
+/// set_max_errors(Some(100));
+/// assert_eq!(max_errors(), Some(100));
+/// # set_max_errors(None);
+/// ```
+pub fn set_max_errors(max: Option<usize>) {
+    MAX_ERRORS.store(max.unwrap_or(usize::MAX), Ordering::Relaxed);
+}
+
+/// Get the maximum number of errors collected in one error set.
+pub fn max_errors() -> Option<usize> {
+    Some(MAX_ERRORS.load(Ordering::Relaxed)).filter(|&v| v != usize::MAX)
+}
+
 /// Any validation error.
 #[derive(Debug, Default)]
 pub struct ValidationError {
@@ -25,6 +59,8 @@ pub struct ValidationError {
     pub validates: Vec<ValidateError>,
     /// Error produced by transformers.
     pub transforms: Vec<TransformError>,
+    /// Whether some errors were dropped because of [`max_errors`].
+    pub truncated: bool,
 }
 
 /// Type alias for `Result<ValidationError>`.
@@ -38,43 +74,76 @@ impl ValidationError {
 
     /// Check if the error set contains any error instance.
     pub fn check(self) -> Result<(), ValidationError> {
-        if self.validates.is_empty() && self.transforms.is_empty() {
-            Ok(())
-        } else {
-            Err(self)
-        }
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+
+    /// Number of errors in the set.
+    pub fn len(&self) -> usize {
+        self.validates.len() + self.transforms.len()
+    }
+
+    /// Check if the set contains no error, including dropped ones.
+    pub fn is_empty(&self) -> bool {
+        self.validates.is_empty() && self.transforms.is_empty() && !self.truncated
+    }
+
+    /// Check if the set has reached [`max_errors`].
+    ///
+    /// Errors pushed to a full set are dropped.
+    pub fn is_full(&self) -> bool {
+        self.truncated || max_errors().is_some_and(|max| self.len() >= max)
     }
 
     /// Push a validator error to the set.
     pub fn push_validate_error(&mut self, err: ValidateError) {
-        self.validates.push(err);
+        if self.reserve() {
+            self.validates.push(err);
+        }
     }
 
     /// Push a transformer error to the set.
     pub fn push_transform_error(&mut self, err: TransformError) {
-        self.transforms.push(err);
+        if self.reserve() {
+            self.transforms.push(err);
+        }
     }
 
     /// Append another error set.
-    pub fn append_error(&mut self, mut rhs: Self) {
-        self.validates.append(&mut rhs.validates);
-        self.transforms.append(&mut rhs.transforms);
+    pub fn append_error(&mut self, rhs: Self) {
+        self.extend_with(rhs, |x| x);
     }
 
     /// Extend the set.
     ///
-    /// This will modify original set's `path` field.
+    /// This will modify original set's `path` field. Errors with an empty
+    /// `path`, which are about the whole value, get the `parent` path.
     pub fn extend_error(&mut self, parent: &str, rhs: Self) {
-        self.validates
-            .extend(rhs.validates.into_iter().map(|mut x| {
-                x.path = format!("{}.{}", parent, x.path);
-                x
-            }));
-        self.transforms
-            .extend(rhs.transforms.into_iter().map(|mut x| {
-                x.path = format!("{}.{}", parent, x.path);
-                x
-            }));
+        self.extend_with(rhs, |x| {
+            if x.is_empty() {
+                parent.to_string()
+            } else {
+                format!("{}.{}", parent, x)
+            }
+        });
+    }
+
+    /// Check if another error fits in the set, and mark the set as truncated
+    /// otherwise.
+    fn reserve(&mut self) -> bool {
+        self.truncated = self.is_full();
+        !self.truncated
+    }
+
+    fn extend_with(&mut self, rhs: Self, mut path: impl FnMut(String) -> String) {
+        for mut x in rhs.validates {
+            x.path = path(x.path);
+            self.push_validate_error(x);
+        }
+        for mut x in rhs.transforms {
+            x.path = path(x.path);
+            self.push_transform_error(x);
+        }
+        self.truncated |= rhs.truncated;
     }
 }
 
@@ -113,6 +182,9 @@ impl display::ErrorDisplay for ValidationError {
             writeln!(w)?;
             Ok(())
         })?;
+        if self.truncated {
+            writeln!(w, "... and more errors were omitted.")?;
+        }
 
         Ok(())
     }
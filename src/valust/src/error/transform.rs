@@ -1,6 +1,9 @@
 //! Transform errors.
 
-use std::fmt::{self, Write};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::fmt::{self, Write};
 
 use sealed::sealed;
 
@@ -11,6 +14,10 @@ use super::ErrorShow;
 pub struct TransformError {
     /// The name of the field where the error occurred.
     pub field: &'static str,
+    /// The human-friendly label of the field.
+    ///
+    /// See [`label`](crate::error::label) for details.
+    pub label: Cow<'static, str>,
     /// The path to the field where the error occurred.
     pub path: String,
     /// The value that caused the error.
@@ -18,7 +25,7 @@ pub struct TransformError {
     /// The underlying cause of the error.
     pub cause: Box<dyn ErrorShow + 'static>,
     /// An optional message providing additional information about the error.
-    pub message: Option<&'static str>,
+    pub message: Option<Cow<'static, str>>,
     /// The expression that caused the error.
     pub expression: &'static str,
     /// The name of the source type involved in the transformation.
@@ -30,7 +37,7 @@ pub struct TransformError {
 #[sealed]
 impl crate::error::display::ErrorDisplay for TransformError {
     fn full_display(&self, w: &mut impl Write) -> fmt::Result {
-        if let Some(msg) = self.message {
+        if let Some(msg) = &self.message {
             writeln!(w, "Transform error: {}", msg)?;
         } else {
             writeln!(w, "Transform error:")?;
@@ -54,7 +61,7 @@ impl crate::error::display::ErrorDisplay for TransformError {
 
     fn brief_display(&self, w: &mut impl Write) -> fmt::Result {
         write!(w, "Transform error: ")?;
-        if let Some(msg) = self.message {
+        if let Some(msg) = &self.message {
             writeln!(w, "{}", msg)?;
         } else {
             writeln!(w, "{}", self.cause)?;
@@ -65,7 +72,7 @@ impl crate::error::display::ErrorDisplay for TransformError {
 
     fn human_readable_display(&self, w: &mut impl Write) -> fmt::Result {
         write!(w, "Transform: ",)?;
-        if let Some(msg) = self.message {
+        if let Some(msg) = &self.message {
             writeln!(w, "{}", msg)?;
         } else {
             writeln!(w, "{}", self.cause)?;
@@ -82,3 +89,35 @@ impl crate::error::display::ErrorDisplay for TransformError {
         Ok(())
     }
 }
+
+/// Error type for transformers that hand their input back on failure.
+///
+/// Fallible transformers declared with `try_ret(...)` return
+/// `Result<T, Rejected<I, E>>` instead of `Result<T, E>`. Since the input is
+/// given back on failure, the macro-generated code does not need to `clone` it
+/// beforehand in order to display it in the error report.
+///
+/// ```rust
+/// use valust::error::transform::Rejected;
+///
+/// fn parse_u8(s: String) -> Result<u8, Rejected<String, std::num::ParseIntError>> {
+///     s.parse().map_err(|cause| Rejected { input: s, cause })
+/// }
+///
+/// let err = parse_u8("256".to_owned()).unwrap_err();
+/// assert_eq!(err.input, "256");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejected<I, E> {
+    /// The input of the transformer.
+    pub input: I,
+    /// The underlying cause of the error.
+    pub cause: E,
+}
+
+impl<I, E> Rejected<I, E> {
+    /// Create a new `Rejected` error.
+    pub fn new(input: I, cause: E) -> Self {
+        Self { input, cause }
+    }
+}
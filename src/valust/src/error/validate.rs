@@ -1,6 +1,10 @@
 //! Validate errors.
 
-use std::fmt::{self, Write};
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
 
 use sealed::sealed;
 
@@ -11,6 +15,10 @@ use super::ErrorShow;
 pub struct ValidateError {
     /// The name of the field that caused the error.
     pub field: &'static str,
+    /// The human-friendly label of the field.
+    ///
+    /// See [`label`](crate::error::label) for details.
+    pub label: Cow<'static, str>,
     /// The path to the field that caused the error.
     pub path: String,
     /// The value that caused the error.
@@ -23,17 +31,62 @@ pub struct ValidateError {
     /// If there's no error found (e.g. the value is simply invalid), then the field will be `None`.
     pub cause: Option<Box<dyn ErrorShow + 'static>>,
     /// An optional message providing additional information about the error.
-    pub message: Option<&'static str>,
+    pub message: Option<Cow<'static, str>>,
     /// The expression that was evaluated and caused the error.
     pub expression: &'static str,
     /// he type name of the value that caused the error.
     pub type_name: &'static str,
+    /// Parameters of the validator, e.g. `[("max", "20")]` for `len(max = 20)`.
+    ///
+    /// Values are `Debug`-formatted.
+    pub params: Vec<(&'static str, String)>,
+    /// Machine-readable code of the violated rule, set by
+    /// [`Validator`](crate::validator::Validator)s.
+    pub code: Option<Cow<'static, str>>,
+}
+
+impl ValidateError {
+    /// Create an error reported by custom validation logic, e.g. by a
+    /// `#[valust(validate_with = ...)]` hook.
+    ///
+    /// `field` is also used as the label and the path of the error.
+    ///
+    /// ```rust
+    /// use valust::error::ValidationError;
+    /// use valust::error::validate::ValidateError;
+    ///
+    /// let mut err = ValidationError::new();
+    /// err.push_validate_error(
+    ///     ValidateError::custom("lines", "line total does not match").with_path("lines.3"),
+    /// );
+    /// assert_eq!(err.validates[0].path, "lines.3");
+    /// ```
+    pub fn custom(field: &'static str, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            field,
+            label: Cow::Borrowed(field),
+            path: field.to_owned(),
+            value: "<custom>".to_owned(),
+            cause: None,
+            message: Some(message.into()),
+            expression: "<custom>",
+            type_name: "<custom>",
+            params: Vec::new(),
+            code: None,
+        }
+    }
+
+    /// Set the path of the error.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
 }
 
 #[sealed]
 impl crate::error::display::ErrorDisplay for ValidateError {
     fn full_display(&self, w: &mut impl Write) -> fmt::Result {
-        if let Some(msg) = self.message {
+        if let Some(msg) = &self.message {
             writeln!(w, "Validate error: {}", msg)?;
         } else {
             writeln!(w, "Validate error:")?;
@@ -47,6 +100,16 @@ impl crate::error::display::ErrorDisplay for ValidateError {
             self.field, self.type_name, self.value
         )?;
         writeln!(w, "Validator: {}", self.expression)?;
+        if let Some(code) = &self.code {
+            writeln!(w, "Code: {}", code)?;
+        }
+        if !self.params.is_empty() {
+            write!(w, "Params:")?;
+            self.params
+                .iter()
+                .try_for_each(|(name, value)| write!(w, " {} = {};", name, value))?;
+            writeln!(w)?;
+        }
         writeln!(w, "Path: {}", self.path)?;
         writeln!(w)?;
 
@@ -55,7 +118,7 @@ impl crate::error::display::ErrorDisplay for ValidateError {
 
     fn brief_display(&self, w: &mut impl Write) -> fmt::Result {
         write!(w, "Validate error [{}]", self.path)?;
-        if let Some(msg) = self.message {
+        if let Some(msg) = &self.message {
             writeln!(w, ": {}", msg)?;
         } else if let Some(cause) = &self.cause {
             writeln!(w, ": {}", cause)?;
@@ -66,7 +129,7 @@ impl crate::error::display::ErrorDisplay for ValidateError {
 
     fn human_readable_display(&self, w: &mut impl Write) -> fmt::Result {
         write!(w, "Validate: ")?;
-        if let Some(msg) = self.message {
+        if let Some(msg) = &self.message {
             writeln!(w, "{}", msg)?;
         } else if let Some(cause) = &self.cause {
             writeln!(w, "{}", cause)?;
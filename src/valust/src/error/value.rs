@@ -0,0 +1,557 @@
+//! Capturing values into error reports.
+//!
+//! Values stored in [`ValidateError::value`](super::validate::ValidateError::value)
+//! and [`TransformError::value`](super::transform::TransformError::value) are
+//! formatted by the macro-generated code through [`capture_value`].
+//!
+//! Huge values (a 50 KB string, a `Vec<u8>` upload, ...) can be summarized by
+//! setting [`ValueLimits`], either globally via [`set_global_limits`] or per field
+//! via `#[valust(value(max_chars = .., max_bytes = .., max_items = ..))]`.
+//! Formatting stops as soon as a limit is reached, so truncated values are cheap
+//! to render as well.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static GLOBAL_MAX_CHARS: AtomicUsize = AtomicUsize::new(usize::MAX);
+static GLOBAL_MAX_BYTES: AtomicUsize = AtomicUsize::new(usize::MAX);
+static GLOBAL_MAX_ITEMS: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Limits applied when capturing a value into an error report.
+///
+/// `None` means "no limit" for [`set_global_limits`], and "inherit the global
+/// limit" for per-field limits.
+///
+/// The inputs of `try(...)` and `with(...)` transformers are captured with the
+/// same limits, into a [`ValueSnapshot`], before the transformer runs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ValueLimits {
+    /// Maximum number of characters shown.
+    pub max_chars: Option<usize>,
+    /// Maximum number of UTF-8 bytes shown, e.g. to fit a database column.
+    ///
+    /// The value is never cut inside of a character.
+    pub max_bytes: Option<usize>,
+    /// Maximum number of elements shown for each `[...]` sequence, i.e. the
+    /// `Debug` output of slices, arrays, `Vec`s and `VecDeque`s.
+    ///
+    /// Fields of structs and tuples, as well as entries of sets and maps, are
+    /// not elided, since they can't be told apart from the `Debug` output.
+    pub max_items: Option<usize>,
+}
+
+impl ValueLimits {
+    /// No limit at all.
+    pub const UNLIMITED: Self = Self {
+        max_chars: None,
+        max_bytes: None,
+        max_items: None,
+    };
+
+    /// Fill unset limits with the global ones.
+    pub fn or_global(self) -> Self {
+        let global = global_limits();
+        Self {
+            max_chars: self.max_chars.or(global.max_chars),
+            max_bytes: self.max_bytes.or(global.max_bytes),
+            max_items: self.max_items.or(global.max_items),
+        }
+    }
+}
+
+/// Set the limits used by all fields without their own limits.
+///
+/// ```rust
+/// use valust::error::value::{ValueLimits, global_limits, set_global_limits};
+///
+/// set_global_limits(ValueLimits {
+///     max_chars: Some(256),
+///     max_bytes: None,
+///     max_items: Some(16),
+/// });
+/// assert_eq!(global_limits().max_chars, Some(256));
+/// # set_global_limits(ValueLimits::UNLIMITED);
+/// ```
+pub fn set_global_limits(limits: ValueLimits) {
+    GLOBAL_MAX_CHARS.store(limits.max_chars.unwrap_or(usize::MAX), Ordering::Relaxed);
+    GLOBAL_MAX_BYTES.store(limits.max_bytes.unwrap_or(usize::MAX), Ordering::Relaxed);
+    GLOBAL_MAX_ITEMS.store(limits.max_items.unwrap_or(usize::MAX), Ordering::Relaxed);
+}
+
+/// Get the limits used by all fields without their own limits.
+pub fn global_limits() -> ValueLimits {
+    let load =
+        |v: &AtomicUsize| Some(v.load(Ordering::Relaxed)).filter(|&v| v != usize::MAX);
+    ValueLimits {
+        max_chars: load(&GLOBAL_MAX_CHARS),
+        max_bytes: load(&GLOBAL_MAX_BYTES),
+        max_items: load(&GLOBAL_MAX_ITEMS),
+    }
+}
+
+/// Format a value as `(<type>) <value>`, applying `limits`.
+///
+/// `len` is the length of the value if known (e.g. for strings and collections),
+/// and is appended as a note when the value gets truncated.
+///
+/// ```rust
+/// use valust::error::value::{ValueLimits, capture_value};
+///
+/// let data = vec![1, 2, 3, 4, 5];
+/// let limits = ValueLimits {
+///     max_items: Some(2),
+///     ..ValueLimits::UNLIMITED
+/// };
+/// let text = capture_value("Vec<i32>", format_args!("{:?}", data), limits, Some(data.len()));
+/// assert_eq!(text, "(Vec<i32>) [1, 2, …] (len = 5)");
+/// ```
+pub fn capture_value(
+    type_name: &str,
+    value: fmt::Arguments<'_>,
+    limits: ValueLimits,
+    len: Option<usize>,
+) -> String {
+    let mut out = String::new();
+    let outcome = summarize(&mut out, value, limits.or_global());
+    finish(type_name, &out, outcome, len)
+}
+
+/// How the formatting of a captured value ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Complete,
+    Truncated,
+    Failed,
+}
+
+/// Format `value` into `out`, applying `limits`.
+fn summarize<W: Write>(
+    out: &mut W,
+    value: fmt::Arguments<'_>,
+    limits: ValueLimits,
+) -> Outcome {
+    let mut writer = Summarize {
+        out,
+        max_chars: limits.max_chars.unwrap_or(usize::MAX),
+        max_bytes: limits.max_bytes.unwrap_or(usize::MAX),
+        max_items: limits.max_items.unwrap_or(usize::MAX),
+        chars: 0,
+        bytes: 0,
+        items: [0; MAX_DEPTH],
+        depth: 0,
+        skip_depth: None,
+        quote: None,
+        quoted_chars: 0,
+        quoted_escape: false,
+        escaped: false,
+        prev: ' ',
+        truncated: false,
+    };
+    let aborted = fmt::write(&mut writer, value).is_err();
+    match (aborted, writer.truncated) {
+        (_, true) => Outcome::Truncated,
+        (true, false) => Outcome::Failed,
+        (false, false) => Outcome::Complete,
+    }
+}
+
+/// Build the report of a summarized value, as `(<type>) <value>` followed by a
+/// note if the value is truncated.
+fn finish(type_name: &str, text: &str, outcome: Outcome, len: Option<usize>) -> String {
+    let mut out = String::with_capacity(type_name.len() + text.len() + 3);
+    out.push('(');
+    out.push_str(type_name);
+    out.push_str(") ");
+    out.push_str(text);
+    match (outcome, len) {
+        (Outcome::Complete, _) => {}
+        (Outcome::Failed, _) => out.push_str("<error while formatting>"),
+        (Outcome::Truncated, Some(len)) => {
+            let _ = write!(out, " (len = {})", len);
+        }
+        (Outcome::Truncated, None) => out.push_str(" (truncated)"),
+    }
+    out
+}
+
+/// Inline capacity of a [`ValueSnapshot`] in bytes.
+///
+/// Longer values are moved to the heap, so that the [`ValueLimits`] of the
+/// snapshot are honoured whatever their size.
+pub const SNAPSHOT_BYTES: usize = 256;
+
+/// A value captured into an inline buffer, i.e. without allocating unless it
+/// is longer than [`SNAPSHOT_BYTES`].
+///
+/// Fallible transformers consume their input, so the macro-generated code
+/// captures it beforehand, and the report is only built if the transformer
+/// fails. The same [`ValueLimits`] apply as to the other errors of the field.
+///
+/// ```rust
+/// use valust::error::value::{ValueLimits, ValueSnapshot};
+///
+/// let data = vec![1, 2, 3];
+/// let limits = ValueLimits {
+///     max_items: Some(2),
+///     ..ValueLimits::UNLIMITED
+/// };
+/// let snapshot = ValueSnapshot::capture(format_args!("{:?}", data), limits, Some(data.len()));
+/// drop(data);
+/// assert_eq!(snapshot.report("Vec<i32>"), "(Vec<i32>) [1, 2, …] (len = 3)");
+/// ```
+pub struct ValueSnapshot {
+    buf: InlineBuf,
+    outcome: Outcome,
+    value_len: Option<usize>,
+}
+
+impl ValueSnapshot {
+    /// Capture `value`, applying `limits`.
+    ///
+    /// `len` is the length of the value if known, see [`capture_value`].
+    pub fn capture(
+        value: fmt::Arguments<'_>,
+        limits: ValueLimits,
+        len: Option<usize>,
+    ) -> Self {
+        let mut buf = InlineBuf {
+            buf: [0; SNAPSHOT_BYTES],
+            len: 0,
+            spilled: None,
+        };
+        let outcome = summarize(&mut buf, value, limits.or_global());
+        Self {
+            buf,
+            outcome,
+            value_len: len,
+        }
+    }
+
+    /// Build the report of the value, as [`capture_value`] does.
+    pub fn report(&self, type_name: &str) -> String {
+        finish(type_name, self.buf.as_str(), self.outcome, self.value_len)
+    }
+}
+
+impl fmt::Debug for ValueSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueSnapshot")
+            .field("text", &self.buf.as_str())
+            .field("outcome", &self.outcome)
+            .field("value_len", &self.value_len)
+            .finish()
+    }
+}
+
+/// Buffer of a [`ValueSnapshot`], moved to the heap once it is full.
+struct InlineBuf {
+    buf: [u8; SNAPSHOT_BYTES],
+    len: usize,
+    spilled: Option<String>,
+}
+
+impl InlineBuf {
+    fn as_str(&self) -> &str {
+        match &self.spilled {
+            Some(text) => text,
+            // only whole `str`s are written to the buffer
+            None => core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default(),
+        }
+    }
+}
+
+impl Write for InlineBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if let Some(text) = &mut self.spilled {
+            text.push_str(s);
+            return Ok(());
+        }
+        let end = self.len + s.len();
+        if end > SNAPSHOT_BYTES {
+            let mut text = String::with_capacity(end.max(2 * SNAPSHOT_BYTES));
+            text.push_str(self.as_str());
+            text.push_str(s);
+            self.spilled = Some(text);
+            return Ok(());
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        if c.is_ascii() && self.spilled.is_none() && self.len < SNAPSHOT_BYTES {
+            self.buf[self.len] = c as u8;
+            self.len += 1;
+            return Ok(());
+        }
+        self.write_str(c.encode_utf8(&mut [0; 4]))
+    }
+}
+
+/// Longest `Debug` output of a `char` between its quotes, i.e. `\u{10ffff}`.
+const MAX_CHAR_LITERAL: usize = 10;
+
+/// Deepest nesting of brackets whose elements are tracked, so that no
+/// allocation is needed. Sequences nested deeper are not elided.
+const MAX_DEPTH: usize = 32;
+
+/// Marks a bracket other than `[` in [`Summarize::items`].
+const NOT_SEQUENCE: usize = usize::MAX;
+
+/// Writer that stops once `max_chars` characters or `max_bytes` bytes are
+/// written, and elides sequence elements after `max_items`.
+///
+/// Sequences are detected from the `Debug`-like output, i.e. by tracking
+/// brackets outside of string and char literals. Only `[...]` is elided, as
+/// `{...}` and `(...)` also delimit the fields of structs and tuples.
+struct Summarize<'a, W> {
+    out: &'a mut W,
+    max_chars: usize,
+    max_bytes: usize,
+    max_items: usize,
+    chars: usize,
+    bytes: usize,
+    /// Elements seen for each open bracket, [`NOT_SEQUENCE`] for brackets
+    /// other than `[`, whose elements are never elided.
+    items: [usize; MAX_DEPTH],
+    /// Number of open brackets.
+    depth: usize,
+    /// Depth of the bracket whose remaining elements are being skipped.
+    skip_depth: Option<usize>,
+    quote: Option<char>,
+    /// Characters seen inside of the current `'` literal.
+    quoted_chars: usize,
+    /// Whether the current `'` literal starts with an escape.
+    quoted_escape: bool,
+    escaped: bool,
+    /// Previous character outside of skipped elements.
+    prev: char,
+    truncated: bool,
+}
+
+impl<W: Write> Summarize<'_, W> {
+    fn emit(&mut self, c: char) -> fmt::Result {
+        if self.chars >= self.max_chars || self.bytes + c.len_utf8() > self.max_bytes {
+            let _ = self.out.write_char('…');
+            self.truncated = true;
+            return Err(fmt::Error);
+        }
+        self.chars += 1;
+        self.bytes += c.len_utf8();
+        self.out.write_char(c)
+    }
+
+    /// Whether `s` leaves the bracket and literal tracking unchanged, so that it
+    /// can be written at once.
+    fn is_plain(&self, s: &str) -> bool {
+        // brackets and literals are only tracked to elide elements
+        if self.max_items == usize::MAX {
+            return true;
+        }
+        let special: &[u8] = match self.quote {
+            None => b"[]{}(),\"'",
+            Some('"') if !self.escaped => b"\"\\",
+            _ => return false,
+        };
+        self.skip_depth.is_none()
+            && !(self.max_items == 0 && self.prev == '[')
+            && !s.bytes().any(|b| special.contains(&b))
+    }
+
+    /// Write `s` at once if it fits, or char by char up to the limits.
+    fn emit_str(&mut self, s: &str) -> fmt::Result {
+        let Some(last) = s.chars().next_back() else {
+            return Ok(());
+        };
+        // characters are only counted if limited
+        let chars = if self.max_chars == usize::MAX {
+            0
+        } else {
+            s.chars().count()
+        };
+        if self.bytes + s.len() > self.max_bytes || self.chars + chars > self.max_chars
+        {
+            return s.chars().try_for_each(|c| self.emit(c));
+        }
+        self.chars += chars;
+        self.bytes += s.len();
+        self.prev = last;
+        self.out.write_str(s)
+    }
+
+    /// Start skipping the remaining elements of the innermost sequence.
+    fn elide(&mut self) {
+        self.skip_depth = Some(self.depth);
+        self.truncated = true;
+    }
+
+    fn open(&mut self, items: usize) {
+        if let Some(slot) = self.items.get_mut(self.depth) {
+            *slot = items;
+        }
+        self.depth += 1;
+    }
+
+    /// Elements seen in the innermost bracket, if it is a tracked sequence.
+    fn sequence_items(&mut self) -> Option<&mut usize> {
+        let slot = self.items.get_mut(self.depth.checked_sub(1)?)?;
+        (*slot != NOT_SEQUENCE).then_some(slot)
+    }
+
+    /// Track literals, returning whether `c` is inside of one.
+    fn in_literal(&mut self, c: char) -> bool {
+        let Some(q) = self.quote else {
+            // a `'` after a word is an apostrophe, e.g. in `Display` output
+            if c == '"' || (c == '\'' && !self.prev.is_alphanumeric()) {
+                self.quote = Some(c);
+                self.quoted_chars = 0;
+                return true;
+            }
+            return false;
+        };
+        if q == '\'' {
+            self.quoted_chars += 1;
+            // a char literal is either `'x'` or an escape like `'\u{10ffff}'`,
+            // anything else follows an unmatched quote
+            let escape = self.quoted_chars > 1 && self.quoted_escape;
+            let literal = match self.quoted_chars {
+                1 => {
+                    self.quoted_escape = c == '\\';
+                    true
+                }
+                2 => c == '\'' || escape,
+                n => escape && n <= MAX_CHAR_LITERAL + 1,
+            };
+            if !literal {
+                self.quote = None;
+                self.escaped = false;
+                return false;
+            }
+        }
+        if self.escaped {
+            self.escaped = false;
+        } else if c == '\\' {
+            self.escaped = true;
+        } else if c == q {
+            self.quote = None;
+        }
+        true
+    }
+}
+
+impl<W: Write> Write for Summarize<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.is_plain(s) {
+            return self.emit_str(s);
+        }
+        s.chars().try_for_each(|c| self.write_char(c))
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        if self.max_items == usize::MAX {
+            return self.emit(c);
+        }
+        // the first element of a sequence, elided if `max_items` is 0
+        if self.max_items == 0
+            && self.skip_depth.is_none()
+            && self.prev == '['
+            && c != ']'
+            && self.quote.is_none()
+            && self.sequence_items().is_some()
+        {
+            self.elide();
+            self.emit('…')?;
+        }
+
+        if !self.in_literal(c) {
+            match c {
+                '[' => self.open(0),
+                '{' | '(' => self.open(NOT_SEQUENCE),
+                ']' | '}' | ')' => {
+                    if self.skip_depth == Some(self.depth) {
+                        self.skip_depth = None;
+                    }
+                    self.depth = self.depth.saturating_sub(1);
+                }
+                ',' if self.skip_depth.is_none() => {
+                    let max_items = self.max_items;
+                    if let Some(items) = self.sequence_items() {
+                        *items += 1;
+                        if *items >= max_items {
+                            self.elide();
+                            self.emit(',')?;
+                            self.emit(' ')?;
+                            self.emit('…')?;
+                            self.prev = c;
+                            return Ok(());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match self.skip_depth {
+            // still inside of the elided elements
+            Some(depth) if self.depth >= depth => {}
+            _ => {
+                self.emit(c)?;
+                self.prev = c;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Length probe used by the macro-generated code.
+///
+/// `(&&LenProbe(&value)).valust_len()` resolves to [`KnownLen`] for strings and
+/// collections, and falls back to [`UnknownLen`] for everything else.
+#[doc(hidden)]
+pub struct LenProbe<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait KnownLen {
+    fn valust_len(&self) -> Option<usize>;
+}
+
+#[doc(hidden)]
+pub trait UnknownLen {
+    fn valust_len(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<T: ?Sized> UnknownLen for LenProbe<'_, T> {}
+
+macro_rules! impl_known_len {
+    ($([$($gen:tt)*] $ty:ty),* $(,)?) => {
+        $(
+            impl<$($gen)*> KnownLen for &LenProbe<'_, $ty> {
+                fn valust_len(&self) -> Option<usize> {
+                    Some(self.0.len())
+                }
+            }
+        )*
+    };
+}
+
+impl_known_len!(
+    [] str,
+    [] String,
+    [T] [T],
+    [T, const N: usize] [T; N],
+    [T] Vec<T>,
+    [T] alloc::collections::VecDeque<T>,
+    [T] alloc::collections::BTreeSet<T>,
+    [K, V] alloc::collections::BTreeMap<K, V>,
+);
+
+#[cfg(feature = "std")]
+impl_known_len!(
+    [T, S] std::collections::HashSet<T, S>,
+    [K, V, S] std::collections::HashMap<K, V, S>,
+);
@@ -0,0 +1,92 @@
+//! `Validate` implementations for common containers.
+//!
+//! Containers are transparent to the nesting depth, so that recursive types
+//! like `Vec<Node>` or `Option<Box<Node>>` can be forwarded. As the root
+//! value, they check the references of their content at once.
+
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::Validate;
+use crate::error::ValidationError;
+use crate::refs::{self, RefCollector};
+
+impl<T: Validate> Validate for Box<T> {
+    type Raw = Box<T::Raw>;
+
+    const HAS_REFS: bool = T::HAS_REFS;
+
+    fn validate(raw: Self::Raw) -> Result<Self, ValidationError> {
+        Self::validate_nested(raw, 0).and_then(refs::check_root)
+    }
+
+    fn validate_nested(raw: Self::Raw, depth: usize) -> Result<Self, ValidationError> {
+        T::validate_nested(*raw, depth).map(Box::new)
+    }
+
+    fn collect_refs(&self, path: &str, collection: &str, refs: &mut RefCollector) {
+        T::collect_refs(self, path, collection, refs)
+    }
+}
+
+impl<T: Validate> Validate for Option<T> {
+    type Raw = Option<T::Raw>;
+
+    const HAS_REFS: bool = T::HAS_REFS;
+
+    fn validate(raw: Self::Raw) -> Result<Self, ValidationError> {
+        Self::validate_nested(raw, 0).and_then(refs::check_root)
+    }
+
+    fn validate_nested(raw: Self::Raw, depth: usize) -> Result<Self, ValidationError> {
+        raw.map(|raw| T::validate_nested(raw, depth)).transpose()
+    }
+
+    fn collect_refs(&self, path: &str, collection: &str, refs: &mut RefCollector) {
+        if let Some(value) = self {
+            value.collect_refs(path, collection, refs)
+        }
+    }
+}
+
+/// Errors of the elements are prefixed with their index.
+impl<T: Validate> Validate for Vec<T> {
+    type Raw = Vec<T::Raw>;
+
+    const HAS_REFS: bool = T::HAS_REFS;
+
+    fn validate(raw: Self::Raw) -> Result<Self, ValidationError> {
+        Self::validate_nested(raw, 0).and_then(refs::check_root)
+    }
+
+    fn validate_nested(raw: Self::Raw, depth: usize) -> Result<Self, ValidationError> {
+        let mut err = ValidationError::new();
+        let mut out = Vec::with_capacity(raw.len());
+        for (idx, raw) in raw.into_iter().enumerate() {
+            match T::validate_nested(raw, depth) {
+                Ok(item) => out.push(item),
+                Err(e) => {
+                    err.extend_error(&idx.to_string(), e);
+                    if err.is_full() {
+                        break;
+                    }
+                }
+            }
+        }
+        err.check().map(|_| out)
+    }
+
+    fn collect_refs(&self, path: &str, collection: &str, refs: &mut RefCollector) {
+        if !T::HAS_REFS {
+            return;
+        }
+        for (idx, item) in self.iter().enumerate() {
+            item.collect_refs(
+                &RefCollector::join(path, &idx.to_string()),
+                collection,
+                refs,
+            );
+        }
+    }
+}
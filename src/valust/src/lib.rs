@@ -1,9 +1,27 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+pub mod captures;
 pub mod error;
+mod impls;
+pub mod refs;
+#[cfg(any(feature = "regex", feature = "regex-lite", feature = "fancy-regex"))]
+mod regex_engine;
+pub mod transformer;
+pub mod validator;
+
+#[doc(hidden)]
+#[path = "private.rs"]
+pub mod __private;
 
+#[cfg(feature = "fancy-regex")]
+pub use fancy_regex;
 #[cfg(feature = "regex")]
 pub use regex;
+#[cfg(feature = "regex-lite")]
+pub use regex_lite;
 #[cfg(feature = "derive")]
 pub use valust_derive as derive;
 
@@ -16,8 +34,43 @@ pub trait Validate: Sized {
     /// The raw data type.
     type Raw;
 
+    /// Whether values may contain `#[valust(id)]` or `#[valust(references)]`
+    /// fields, i.e. whether [`collect_refs`](Self::collect_refs) may collect
+    /// anything. See [`refs`] for details.
+    const HAS_REFS: bool = false;
+
     /// Validates the raw data and returns the validated data or an error.
     fn validate(raw: Self::Raw) -> Result<Self, error::ValidationError>;
+
+    /// Validates raw data nested `depth` levels deep in another structure.
+    ///
+    /// Forwarded fields are validated with this method, so that
+    /// `#[valust(max_depth = N)]` can reject deeply nested data. The default
+    /// implementation ignores the depth.
+    fn validate_nested(
+        raw: Self::Raw,
+        depth: usize,
+    ) -> Result<Self, error::ValidationError> {
+        let _ = depth;
+        Self::validate(raw)
+    }
+
+    /// Collects the identifiers and references of a validated value.
+    ///
+    /// Implementations collecting anything must set
+    /// [`HAS_REFS`](Self::HAS_REFS).
+    ///
+    /// `path` is the path of the value, and `collection` is the name of the
+    /// collection its `#[valust(id)]` fields belong to. See [`refs`] for
+    /// details. The default implementation collects nothing.
+    fn collect_refs(
+        &self,
+        path: &str,
+        collection: &str,
+        refs: &mut refs::RefCollector,
+    ) {
+        let _ = (path, collection, refs);
+    }
 }
 
 /// A type alias for the raw data type of a validated data type.
@@ -0,0 +1,567 @@
+//! Runtime helpers for the macro-generated code.
+//!
+//! Error construction lives here instead of being expanded inline for every
+//! validator and transformer. All helpers are `#[cold]` and non-generic, so
+//! they are compiled only once and kept out of the success path.
+//!
+//! **This module is not part of the public API.**
+
+use alloc::borrow::{Cow, ToOwned};
+/// `alloc` might not be in scope of the user crate under `no_std`.
+pub use alloc::boxed::Box;
+use alloc::format;
+pub use alloc::string::String;
+pub use alloc::vec::Vec;
+use core::any::type_name;
+use core::fmt;
+
+#[cfg(any(feature = "regex", feature = "regex-lite", feature = "fancy-regex"))]
+use crate::captures::{CaptureFailure, FromCaptures, NoMatch};
+use crate::error::label::{localize, render_message};
+use crate::error::transform::TransformError;
+use crate::error::validate::ValidateError;
+pub use crate::error::value::{KnownLen, LenProbe, UnknownLen};
+use crate::error::value::{ValueLimits, ValueSnapshot, capture_value};
+use crate::error::{ErrorShow, ValidationError};
+#[cfg(any(feature = "regex", feature = "regex-lite", feature = "fancy-regex"))]
+pub use crate::regex_engine::{CachedRegex, Regex, RegexError};
+use crate::transformer::Transformer;
+use crate::validator::Violation;
+
+/// Static information about a field validator.
+pub struct ValidateSite {
+    pub field: &'static str,
+    pub label: &'static str,
+    pub message: Option<&'static str>,
+    pub expression: &'static str,
+    pub type_name: &'static str,
+    pub limits: ValueLimits,
+}
+
+/// Static information about a field transformer.
+pub struct TransformSite {
+    pub field: &'static str,
+    pub label: &'static str,
+    pub message: Option<&'static str>,
+    pub expression: &'static str,
+    pub source_type_name: &'static str,
+    pub target_type_name: &'static str,
+    pub limits: ValueLimits,
+}
+
+/// Static information about a `Transformer` applied by `trans(with(...))`.
+///
+/// Type names are taken from the transformer, see [`TransformerFailure`].
+pub struct TransformerSite {
+    pub field: &'static str,
+    pub label: &'static str,
+    pub message: Option<&'static str>,
+    pub expression: &'static str,
+    pub limits: ValueLimits,
+}
+
+/// Error of a `Transformer`, with the names of its source and target types.
+pub struct TransformerFailure {
+    cause: Box<dyn ErrorShow + 'static>,
+    source_type_name: &'static str,
+    target_type_name: &'static str,
+}
+
+/// Apply a `Transformer`, recording its type names on failure.
+#[inline]
+pub fn apply_transformer<I, O, T: Transformer<I, O> + ?Sized>(
+    transformer: &T,
+    input: I,
+) -> Result<O, TransformerFailure> {
+    transformer
+        .transform(input)
+        .map_err(|cause| TransformerFailure {
+            cause: Box::new(cause),
+            source_type_name: type_name::<I>(),
+            target_type_name: type_name::<O>(),
+        })
+}
+
+/// Capture the input of a fallible transformer before it is consumed.
+///
+/// `value` is `None` if the value is not captured.
+#[inline]
+pub fn snapshot(
+    value: Option<fmt::Arguments<'_>>,
+    limits: ValueLimits,
+    len: Option<usize>,
+) -> Option<ValueSnapshot> {
+    value.map(|value| ValueSnapshot::capture(value, limits, len))
+}
+
+fn report_snapshot(type_name: &str, value: Option<&ValueSnapshot>) -> String {
+    match value {
+        Some(value) => value.report(type_name),
+        None => format!("({})", type_name),
+    }
+}
+
+fn format_value(
+    type_name: &str,
+    value: Option<fmt::Arguments<'_>>,
+    limits: ValueLimits,
+    len: Option<usize>,
+) -> String {
+    match value {
+        Some(value) => capture_value(type_name, value, limits, len),
+        None => format!("({})", type_name),
+    }
+}
+
+/// Push a `ValidateError` described by `site`.
+///
+/// `value` is `None` if the value is not captured. `{<name>}` placeholders of
+/// the message are replaced with the `params`.
+#[cold]
+#[inline(never)]
+pub fn validate_error(
+    err: &mut ValidationError,
+    site: &'static ValidateSite,
+    value: Option<fmt::Arguments<'_>>,
+    len: Option<usize>,
+    cause: Option<Box<dyn ErrorShow + 'static>>,
+    params: &[(&'static str, &dyn fmt::Debug)],
+) {
+    let label = localize(site.field, site.label);
+    let params = params
+        .iter()
+        .map(|(name, value)| (*name, format!("{:?}", value)))
+        .collect::<Vec<_>>();
+    let message = site
+        .message
+        .map(|m| render_params(render_message(m, &label), &params));
+    err.push_validate_error(ValidateError {
+        field: site.field,
+        path: site.field.to_owned(),
+        value: format_value(site.type_name, value, site.limits, len),
+        cause,
+        message,
+        label,
+        expression: site.expression,
+        type_name: site.type_name,
+        params,
+        code: None,
+    });
+}
+
+/// Push the `ValidateError` of a `Violation` reported by `#[valid(with(...))]`.
+///
+/// `site.message` is the placeholder of the label in the violation's message,
+/// e.g. `` `{label}` ``.
+#[cold]
+#[inline(never)]
+pub fn violation_error(
+    err: &mut ValidationError,
+    site: &'static ValidateSite,
+    value: Option<fmt::Arguments<'_>>,
+    len: Option<usize>,
+    violation: Violation,
+) {
+    let label = localize(site.field, site.label);
+    let slot = render_message(site.message.unwrap_or("{label}"), &label);
+    let message = Cow::Owned(violation.message.replace("{label}", &slot));
+    err.push_validate_error(ValidateError {
+        field: site.field,
+        path: site.field.to_owned(),
+        value: format_value(site.type_name, value, site.limits, len),
+        cause: None,
+        message: Some(render_params(message, &violation.params)),
+        label,
+        expression: site.expression,
+        type_name: site.type_name,
+        params: violation.params,
+        code: Some(violation.code),
+    });
+}
+
+/// A field of a presence rule, as its external name, label and the
+/// placeholder of the label in default messages.
+pub type PresenceField = (&'static str, &'static str, &'static str);
+
+/// Push the `ValidateError` of a presence rule, e.g. `exactly_one_of(a, b)`.
+///
+/// `{fields}` in `site.message` is replaced by the translated labels of all
+/// the `fields` of the rule.
+#[cold]
+#[inline(never)]
+pub fn presence_error(
+    err: &mut ValidationError,
+    site: &'static ValidateSite,
+    fields: &'static [PresenceField],
+) {
+    let labels = fields
+        .iter()
+        .map(|(field, label, slot)| render_message(slot, &localize(field, label)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let label = localize(site.field, site.label);
+    let message = site
+        .message
+        .map(|m| render_message(m, &label).replace("{fields}", &labels));
+    err.push_validate_error(ValidateError {
+        field: site.field,
+        path: site.field.to_owned(),
+        value: format_value(site.type_name, None, site.limits, None),
+        cause: None,
+        message: message.map(Cow::Owned),
+        label,
+        expression: site.expression,
+        type_name: site.type_name,
+        params: Vec::new(),
+        code: None,
+    });
+}
+
+/// Replace the `{<name>}` placeholders of a message with the `params`.
+fn render_params(
+    message: Cow<'static, str>,
+    params: &[(&'static str, String)],
+) -> Cow<'static, str> {
+    params.iter().fold(message, |message, (name, value)| {
+        let slot = format!("{{{}}}", name);
+        if message.contains(&slot) {
+            Cow::Owned(message.replace(&slot, value))
+        } else {
+            message
+        }
+    })
+}
+
+/// Push a `TransformError` described by `site`.
+///
+/// `value` is `None` if the value is not captured.
+#[cold]
+#[inline(never)]
+pub fn transform_error(
+    err: &mut ValidationError,
+    site: &'static TransformSite,
+    value: Option<&ValueSnapshot>,
+    cause: Box<dyn ErrorShow + 'static>,
+) {
+    let label = localize(site.field, site.label);
+    err.push_transform_error(TransformError {
+        field: site.field,
+        path: site.field.to_owned(),
+        value: report_snapshot(site.source_type_name, value),
+        cause,
+        message: site.message.map(|m| render_message(m, &label)),
+        label,
+        expression: site.expression,
+        source_type_name: site.source_type_name,
+        target_type_name: site.target_type_name,
+    });
+}
+
+/// Push the `TransformError` of a `Transformer` applied by `trans(with(...))`.
+#[cold]
+#[inline(never)]
+pub fn transformer_error(
+    err: &mut ValidationError,
+    site: &'static TransformerSite,
+    value: Option<&ValueSnapshot>,
+    failure: TransformerFailure,
+) {
+    let label = localize(site.field, site.label);
+    err.push_transform_error(TransformError {
+        field: site.field,
+        path: site.field.to_owned(),
+        value: report_snapshot(failure.source_type_name, value),
+        cause: failure.cause,
+        message: site.message.map(|m| render_message(m, &label)),
+        label,
+        expression: site.expression,
+        source_type_name: failure.source_type_name,
+        target_type_name: failure.target_type_name,
+    });
+}
+
+/// Failure of `trans(captures(...))`.
+#[cfg(any(feature = "regex", feature = "regex-lite", feature = "fancy-regex"))]
+pub enum CapturesFailure {
+    Regex(RegexError),
+    NoMatch,
+    Groups(Vec<CaptureFailure>),
+}
+
+/// Match `input` against `regex` and build a `T` from the captures.
+#[cfg(any(feature = "regex", feature = "regex-lite", feature = "fancy-regex"))]
+pub fn parse_captures<T: FromCaptures>(
+    regex: &CachedRegex,
+    input: &str,
+) -> Result<T, CapturesFailure> {
+    let captures = regex
+        .captures(input)
+        .map_err(CapturesFailure::Regex)?
+        .ok_or(CapturesFailure::NoMatch)?;
+    let groups = |name: &'static str| captures.name(name).map(|m| m.as_str());
+    let mut failures = Vec::new();
+    T::from_captures(&groups, &mut failures).ok_or(CapturesFailure::Groups(failures))
+}
+
+/// Compile-time check of `trans(captures(...))`, failing if any of `required`
+/// groups is not one of `groups` of the regex.
+#[cfg(any(feature = "regex", feature = "regex-lite", feature = "fancy-regex"))]
+pub const fn check_groups(
+    required: &[&'static str],
+    groups: &[&str],
+    target: &str,
+) -> GroupCheck {
+    let mut i = 0;
+    while i < required.len() {
+        let mut j = 0;
+        while j < groups.len() && !str_eq(required[i], groups[j]) {
+            j += 1;
+        }
+        if j == groups.len() {
+            return GroupCheck::missing(required[i], target);
+        }
+        i += 1;
+    }
+    GroupCheck {
+        buf: [0; GroupCheck::CAPACITY],
+        len: 0,
+    }
+}
+
+/// Result of [`check_groups`], with the message of a missing group.
+///
+/// Const panics can not format their message, so it is written here.
+#[cfg(any(feature = "regex", feature = "regex-lite", feature = "fancy-regex"))]
+pub struct GroupCheck {
+    buf: [u8; GroupCheck::CAPACITY],
+    len: usize,
+}
+
+#[cfg(any(feature = "regex", feature = "regex-lite", feature = "fancy-regex"))]
+impl GroupCheck {
+    const CAPACITY: usize = 256;
+
+    const fn missing(group: &str, target: &str) -> Self {
+        let mut check = Self {
+            buf: [0; Self::CAPACITY],
+            len: 0,
+        };
+        let parts = [
+            "the regex has no group named `",
+            group,
+            "`, read by `",
+            target,
+            "`",
+        ];
+        let mut i = 0;
+        while i < parts.len() {
+            let part = parts[i].as_bytes();
+            if check.len + part.len() > Self::CAPACITY {
+                // whole parts only, so that the message stays UTF-8
+                break;
+            }
+            let mut j = 0;
+            while j < part.len() {
+                check.buf[check.len] = part[j];
+                check.len += 1;
+                j += 1;
+            }
+            i += 1;
+        }
+        check
+    }
+
+    /// Message of the missing group, if any.
+    pub const fn message(&self) -> Option<&str> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: `buf` is filled with whole `str`s up to `len`
+        Some(unsafe {
+            core::str::from_utf8_unchecked(core::slice::from_raw_parts(
+                self.buf.as_ptr(),
+                self.len,
+            ))
+        })
+    }
+}
+
+#[cfg(any(feature = "regex", feature = "regex-lite", feature = "fancy-regex"))]
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Push the `TransformError`s of `trans(captures(...))`.
+///
+/// A value not matching the regex is reported once, with `site`. Each failing
+/// group is reported with the path `<field>.<group>`, and its text as the
+/// value if `value` is captured.
+#[cfg(any(feature = "regex", feature = "regex-lite", feature = "fancy-regex"))]
+#[cold]
+#[inline(never)]
+pub fn captures_error(
+    err: &mut ValidationError,
+    site: &'static TransformSite,
+    value: Option<fmt::Arguments<'_>>,
+    len: Option<usize>,
+    failure: CapturesFailure,
+) {
+    let failures = match failure {
+        CapturesFailure::Regex(e) => {
+            let value = snapshot(value, site.limits, len);
+            return transform_error(err, site, value.as_ref(), Box::new(e));
+        }
+        CapturesFailure::NoMatch => {
+            let value = snapshot(value, site.limits, len);
+            return transform_error(err, site, value.as_ref(), Box::new(NoMatch));
+        }
+        CapturesFailure::Groups(failures) => failures,
+    };
+    let show_values = value.is_some();
+    for failure in failures {
+        let text = match (&failure.text, show_values) {
+            (Some(text), true) => capture_value(
+                "&str",
+                format_args!("{:?}", text),
+                site.limits,
+                Some(text.len()),
+            ),
+            (None, true) => "<missing>".to_owned(),
+            (_, false) => "(&str)".to_owned(),
+        };
+        err.push_transform_error(TransformError {
+            field: site.field,
+            label: localize(site.field, site.label),
+            path: format!("{}.{}", site.field, failure.group),
+            value: text,
+            cause: failure.cause,
+            message: None,
+            expression: site.expression,
+            source_type_name: "&str",
+            target_type_name: failure.type_name,
+        });
+    }
+}
+
+/// Push a struct-level `ValidateError`.
+#[cold]
+#[inline(never)]
+pub fn meta_error(
+    err: &mut ValidationError,
+    message: Option<&'static str>,
+    expression: &'static str,
+    cause: Option<Box<dyn ErrorShow + 'static>>,
+) {
+    err.push_validate_error(ValidateError {
+        field: "<meta>",
+        label: Cow::Borrowed("<meta>"),
+        path: "<meta>".to_owned(),
+        value: "<meta>".to_owned(),
+        cause,
+        message: message.map(Cow::Borrowed),
+        expression,
+        type_name: "<meta>",
+        params: Vec::new(),
+        code: None,
+    });
+}
+
+/// Number of extended grapheme clusters of `s`, for `grapheme_len(...)`.
+#[cfg(feature = "grapheme")]
+pub fn grapheme_len(s: &str) -> usize {
+    unicode_segmentation::UnicodeSegmentation::graphemes(s, true).count()
+}
+
+/// Whether `s` only has printable characters, for `printable`.
+///
+/// Characters of the `Other` general categories (control, format, surrogate,
+/// private-use and unassigned) and line and paragraph separators are not
+/// printable.
+#[cfg(feature = "general-category")]
+pub fn is_printable(s: &str) -> bool {
+    use unicode_properties::{GeneralCategory, UnicodeGeneralCategory};
+
+    !s.chars().any(|c| {
+        matches!(
+            c.general_category(),
+            GeneralCategory::Control
+                | GeneralCategory::Format
+                | GeneralCategory::Surrogate
+                | GeneralCategory::PrivateUse
+                | GeneralCategory::Unassigned
+                | GeneralCategory::LineSeparator
+                | GeneralCategory::ParagraphSeparator
+        )
+    })
+}
+
+/// Push the error of `#[valust(max_depth = N)]`.
+///
+/// The error is about the whole value, so its path is empty and filled in by
+/// the parent structure.
+#[cold]
+#[inline(never)]
+pub fn depth_error(
+    err: &mut ValidationError,
+    message: &'static str,
+    expression: &'static str,
+    type_name: &'static str,
+) {
+    err.push_validate_error(ValidateError {
+        field: "<depth>",
+        label: Cow::Borrowed("<depth>"),
+        path: String::new(),
+        value: "<depth>".to_owned(),
+        cause: None,
+        message: Some(Cow::Borrowed(message)),
+        expression,
+        type_name,
+        params: Vec::new(),
+        code: None,
+    });
+}
+
+/// Unwrap the result of an infallible transformer.
+pub fn infallible<T>(result: Result<T, core::convert::Infallible>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(never) => match never {},
+    }
+}
+
+/// Output of `#[pre_trans(...)]` expressions.
+///
+/// `()` never fails, `false` fails without a cause, and `Err(e)` fails with
+/// `e` as the cause.
+pub trait PreTransOutput {
+    fn into_result(self) -> Result<(), Option<Box<dyn ErrorShow + 'static>>>;
+}
+
+impl PreTransOutput for () {
+    fn into_result(self) -> Result<(), Option<Box<dyn ErrorShow + 'static>>> {
+        Ok(())
+    }
+}
+
+impl PreTransOutput for bool {
+    fn into_result(self) -> Result<(), Option<Box<dyn ErrorShow + 'static>>> {
+        if self { Ok(()) } else { Err(None) }
+    }
+}
+
+impl<E: ErrorShow + 'static> PreTransOutput for Result<(), E> {
+    fn into_result(self) -> Result<(), Option<Box<dyn ErrorShow + 'static>>> {
+        self.map_err(|e| Some(Box::new(e) as Box<dyn ErrorShow>))
+    }
+}
@@ -0,0 +1,273 @@
+//! Referential integrity inside a document.
+//!
+//! Fields marked `#[valust(id)]` register identifiers in the collection named
+//! after the nearest forwarded field, e.g. `products` for the elements of
+//! `#[forward] products: Vec<Product>`. Fields marked
+//! `#[valust(references = "products")]` refer to these identifiers.
+//!
+//! Once the root value is validated by [`Validate::validate`], it is walked
+//! with [`Validate::collect_refs`], and duplicate identifiers and dangling
+//! references are reported with their full paths. The root may also be a
+//! container, e.g. a `Vec<Order>`, whose elements are checked together.
+//! Values nested in another one, i.e. validated by
+//! [`Validate::validate_nested`], are never checked on their own.
+//!
+//! Identifiers are compared by their string form, see [`RefKey`].
+
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::Validate;
+use crate::error::ValidationError;
+use crate::error::label::{localize, render_message};
+use crate::error::validate::ValidateError;
+
+/// Types usable as identifiers and references.
+///
+/// A value may stand for any number of keys, e.g. `Option<T>` for none or one,
+/// and `Vec<T>` for many.
+pub trait RefKey {
+    /// Call `f` with the string form of each key.
+    fn for_each_key(&self, f: &mut dyn FnMut(String));
+}
+
+impl RefKey for str {
+    fn for_each_key(&self, f: &mut dyn FnMut(String)) {
+        f(self.to_owned())
+    }
+}
+
+impl RefKey for String {
+    fn for_each_key(&self, f: &mut dyn FnMut(String)) {
+        f(self.clone())
+    }
+}
+
+impl RefKey for Cow<'_, str> {
+    fn for_each_key(&self, f: &mut dyn FnMut(String)) {
+        f(self.clone().into_owned())
+    }
+}
+
+macro_rules! impl_ref_key_display {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl RefKey for $ty {
+                fn for_each_key(&self, f: &mut dyn FnMut(String)) {
+                    f(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_ref_key_display!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, bool
+);
+
+impl<T: RefKey + ?Sized> RefKey for &T {
+    fn for_each_key(&self, f: &mut dyn FnMut(String)) {
+        (**self).for_each_key(f)
+    }
+}
+
+impl<T: RefKey + ?Sized> RefKey for Box<T> {
+    fn for_each_key(&self, f: &mut dyn FnMut(String)) {
+        (**self).for_each_key(f)
+    }
+}
+
+impl<T: RefKey> RefKey for Option<T> {
+    fn for_each_key(&self, f: &mut dyn FnMut(String)) {
+        if let Some(key) = self {
+            key.for_each_key(f)
+        }
+    }
+}
+
+impl<T: RefKey> RefKey for [T] {
+    fn for_each_key(&self, f: &mut dyn FnMut(String)) {
+        self.iter().for_each(|key| key.for_each_key(f))
+    }
+}
+
+impl<T: RefKey> RefKey for Vec<T> {
+    fn for_each_key(&self, f: &mut dyn FnMut(String)) {
+        self.as_slice().for_each_key(f)
+    }
+}
+
+/// Static information about an `id` or `references` field.
+pub struct RefSite {
+    /// External name of the field.
+    pub field: &'static str,
+    /// Label of the field, see [`label`](crate::error::label).
+    pub label: &'static str,
+    /// Message of the error, with an optional `{label}` placeholder.
+    pub message: &'static str,
+    /// The `valust` option marking the field.
+    pub expression: &'static str,
+    /// Type name of the field.
+    pub type_name: &'static str,
+}
+
+struct Entry {
+    site: &'static RefSite,
+    collection: String,
+    key: String,
+    path: String,
+}
+
+/// Identifiers and references found in a validated value.
+#[derive(Default)]
+pub struct RefCollector {
+    ids: Vec<Entry>,
+    refs: Vec<Entry>,
+}
+
+impl RefCollector {
+    /// Create an empty collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Join the path of a structure with the name of one of its fields.
+    pub fn join(path: &str, key: &str) -> String {
+        if path.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    }
+
+    /// Register the identifiers of `value` in `collection`.
+    pub fn push_id(
+        &mut self,
+        site: &'static RefSite,
+        collection: &str,
+        value: &(impl RefKey + ?Sized),
+        path: String,
+    ) {
+        value.for_each_key(&mut |key| {
+            self.ids.push(Entry {
+                site,
+                collection: collection.to_owned(),
+                key,
+                path: path.clone(),
+            })
+        });
+    }
+
+    /// Register the references of `value` to `collection`.
+    pub fn push_ref(
+        &mut self,
+        site: &'static RefSite,
+        collection: &str,
+        value: &(impl RefKey + ?Sized),
+        path: String,
+    ) {
+        value.for_each_key(&mut |key| {
+            self.refs.push(Entry {
+                site,
+                collection: collection.to_owned(),
+                key,
+                path: path.clone(),
+            })
+        });
+    }
+
+    /// Report duplicate identifiers, then dangling references.
+    ///
+    /// ```rust
+    /// use valust::error::ValidationError;
+    /// use valust::refs::{RefCollector, RefSite};
+    ///
+    /// static SITE: RefSite = RefSite {
+    ///     field: "sku",
+    ///     label: "sku",
+    ///     message: "`{label}` is invalid",
+    ///     expression: "id",
+    ///     type_name: "String",
+    /// };
+    ///
+    /// let mut refs = RefCollector::new();
+    /// refs.push_id(&SITE, "products", "a", "products.0.sku".into());
+    /// refs.push_id(&SITE, "products", "a", "products.1.sku".into());
+    /// refs.push_ref(&SITE, "products", "b", "orders.0.sku".into());
+    ///
+    /// let mut err = ValidationError::new();
+    /// refs.check(&mut err);
+    /// let paths: Vec<_> = err.validates.iter().map(|e| e.path.as_str()).collect();
+    /// assert_eq!(paths, ["products.1.sku", "orders.0.sku"]);
+    /// ```
+    pub fn check(self, err: &mut ValidationError) {
+        // collection -> key -> path of the first definition
+        let mut known = BTreeMap::<String, BTreeMap<String, String>>::new();
+        for id in self.ids {
+            let ids = known.entry(id.collection.clone()).or_default();
+            match ids.get(&id.key) {
+                Some(first) => {
+                    let message =
+                        format!("{}, first defined at `{}`", id.message(), first);
+                    id.report(err, message);
+                }
+                None => {
+                    ids.insert(id.key, id.path);
+                }
+            }
+        }
+        for reference in self.refs {
+            let found = known
+                .get(&reference.collection)
+                .is_some_and(|ids| ids.contains_key(&reference.key));
+            if !found {
+                let message = reference.message().into_owned();
+                reference.report(err, message);
+            }
+        }
+    }
+}
+
+/// Check the identifiers and references of a validated root value.
+///
+/// Nothing is collected if the type has no `id` or `references` fields, see
+/// [`Validate::HAS_REFS`].
+pub fn check_root<T: Validate>(value: T) -> Result<T, ValidationError> {
+    if !T::HAS_REFS {
+        return Ok(value);
+    }
+    let mut refs = RefCollector::new();
+    value.collect_refs("", "", &mut refs);
+    let mut err = ValidationError::new();
+    refs.check(&mut err);
+    err.check().map(|_| value)
+}
+
+impl Entry {
+    fn label(&self) -> Cow<'static, str> {
+        localize(self.site.field, self.site.label)
+    }
+
+    fn message(&self) -> Cow<'static, str> {
+        render_message(self.site.message, &self.label())
+    }
+
+    fn report(self, err: &mut ValidationError, message: String) {
+        err.push_validate_error(ValidateError {
+            field: self.site.field,
+            label: self.label(),
+            path: self.path,
+            value: self.key,
+            cause: None,
+            message: Some(Cow::Owned(message)),
+            expression: self.site.expression,
+            type_name: self.site.type_name,
+            params: Vec::new(),
+            code: None,
+        });
+    }
+}
@@ -0,0 +1,74 @@
+//! The regex engine of `valid(regex)`, the regex aliases and
+//! `trans(captures(...))`, selected by feature.
+//!
+//! If several engines are enabled, `fancy-regex` takes precedence over `regex`,
+//! which takes precedence over `regex-lite`, so that every pattern accepted by
+//! an enabled engine is supported.
+
+use std::sync::OnceLock;
+
+#[cfg(feature = "fancy-regex")]
+use fancy_regex as engine;
+#[cfg(all(feature = "regex", not(feature = "fancy-regex")))]
+use regex as engine;
+#[cfg(all(
+    feature = "regex-lite",
+    not(any(feature = "regex", feature = "fancy-regex"))
+))]
+use regex_lite as engine;
+
+/// Compiled regex of the selected engine.
+pub type Regex = engine::Regex;
+/// Error of the selected engine, when compiling a regex or, for
+/// `fancy-regex`, when matching exceeds the backtrack limit.
+pub type RegexError = engine::Error;
+
+#[cfg(feature = "fancy-regex")]
+type Captures<'h> = engine::Captures<'h, str>;
+#[cfg(not(feature = "fancy-regex"))]
+type Captures<'h> = engine::Captures<'h>;
+
+/// A regex compiled on first use.
+///
+/// Unlike `LazyLock`, `OnceLock` is available on the crate's MSRV.
+pub struct CachedRegex {
+    init: fn() -> Result<Regex, RegexError>,
+    regex: OnceLock<Result<Regex, RegexError>>,
+}
+
+impl CachedRegex {
+    /// Create a regex compiled by `init` on first use.
+    pub const fn new(init: fn() -> Result<Regex, RegexError>) -> Self {
+        Self {
+            init,
+            regex: OnceLock::new(),
+        }
+    }
+
+    /// The compiled regex, or the error of compiling it.
+    pub fn get(&self) -> Result<&Regex, RegexError> {
+        self.regex
+            .get_or_init(self.init)
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Whether `haystack` matches the regex.
+    pub fn is_match(&self, haystack: &str) -> Result<bool, RegexError> {
+        #[cfg(feature = "fancy-regex")]
+        return self.get()?.is_match(haystack);
+        #[cfg(not(feature = "fancy-regex"))]
+        return Ok(self.get()?.is_match(haystack));
+    }
+
+    /// The groups of the first match in `haystack`.
+    pub(crate) fn captures<'h>(
+        &self,
+        haystack: &'h str,
+    ) -> Result<Option<Captures<'h>>, RegexError> {
+        #[cfg(feature = "fancy-regex")]
+        return self.get()?.captures(haystack);
+        #[cfg(not(feature = "fancy-regex"))]
+        return Ok(self.get()?.captures(haystack));
+    }
+}
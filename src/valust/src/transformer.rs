@@ -0,0 +1,373 @@
+//! Reusable transformer objects.
+//!
+//! A [`Transformer<I, O>`] converts an `I` into an `O`, or fails with its
+//! [`Error`](Transformer::Error). Transformers can be named, shared across
+//! crates and chained into pipelines, unlike the expressions passed to
+//! `#[trans(...)]`.
+//!
+//! ```rust
+//! use valust::transformer::{Lowercase, Transformer, Trim, parse};
+//!
+//! let pipeline = Trim.then(Lowercase);
+//! assert_eq!(pipeline.transform("  Ada ".to_owned()).unwrap(), "ada");
+//!
+//! let port = Trim.then(parse::<u16>());
+//! assert_eq!(port.transform(" 8080".to_owned()).unwrap(), 8080);
+//! assert!(port.transform("http".to_owned()).is_err());
+//! ```
+//!
+//! Closures returning a `Result` are transformers too, e.g.
+//! `Trim.then(|s: String| s.parse::<u16>())`.
+//!
+//! The derive macro applies transformers with `#[trans(with(...))]`, whose
+//! [`TransformError`](crate::error::transform::TransformError)s get the
+//! source and target type names from [`core::any::type_name`].
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use crate::error::ErrorShow;
+
+/// A reusable conversion from `I` to `O`.
+///
+/// The combinators take the intermediate types as parameters, so that they
+/// are inferred where the pipeline is used.
+pub trait Transformer<I, O> {
+    /// Error of a failed conversion, [`Infallible`] if the conversion never
+    /// fails.
+    type Error: ErrorShow + 'static;
+
+    /// Convert the input.
+    fn transform(&self, input: I) -> Result<O, Self::Error>;
+
+    /// Feed the output to `next`.
+    fn then<P, V: Transformer<O, P>>(self, next: V) -> Then<Self, V, O>
+    where
+        Self: Sized,
+    {
+        Then {
+            first: self,
+            second: next,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Convert the error with `f`.
+    fn map_err<E, F>(self, f: F) -> MapErr<Self, F>
+    where
+        Self: Sized,
+        F: Fn(Self::Error) -> E,
+    {
+        MapErr { inner: self, f }
+    }
+
+    /// Convert the input with `fallback` if this transformer fails.
+    ///
+    /// The input is cloned beforehand, and the error of `fallback` is reported
+    /// if both fail.
+    fn or_else<V: Transformer<I, O>>(self, fallback: V) -> OrElse<Self, V>
+    where
+        Self: Sized,
+        I: Clone,
+    {
+        OrElse {
+            first: self,
+            fallback,
+        }
+    }
+
+    /// Convert `Option<I>` into `Option<O>`, leaving `None` as is.
+    fn optional(self) -> Optional<Self>
+    where
+        Self: Sized,
+    {
+        Optional { inner: self }
+    }
+}
+
+impl<I, O, E, F> Transformer<I, O> for F
+where
+    F: Fn(I) -> Result<O, E>,
+    E: ErrorShow + 'static,
+{
+    type Error = E;
+
+    fn transform(&self, input: I) -> Result<O, E> {
+        self(input)
+    }
+}
+
+/// Error of [`Then`], from either step of the pipeline.
+#[derive(Debug)]
+pub enum ThenError<A, B> {
+    /// The first transformer failed.
+    First(A),
+    /// The second transformer failed.
+    Second(B),
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for ThenError<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThenError::First(e) => e.fmt(f),
+            ThenError::Second(e) => e.fmt(f),
+        }
+    }
+}
+
+/// Transformer created by [`Transformer::then`].
+pub struct Then<A, B, M> {
+    first: A,
+    second: B,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<I, M, O, A, B> Transformer<I, O> for Then<A, B, M>
+where
+    A: Transformer<I, M>,
+    B: Transformer<M, O>,
+{
+    type Error = ThenError<A::Error, B::Error>;
+
+    fn transform(&self, input: I) -> Result<O, Self::Error> {
+        let middle = self.first.transform(input).map_err(ThenError::First)?;
+        self.second.transform(middle).map_err(ThenError::Second)
+    }
+}
+
+/// Transformer created by [`Transformer::map_err`].
+pub struct MapErr<T, F> {
+    inner: T,
+    f: F,
+}
+
+impl<I, O, E, T, F> Transformer<I, O> for MapErr<T, F>
+where
+    T: Transformer<I, O>,
+    F: Fn(T::Error) -> E,
+    E: ErrorShow + 'static,
+{
+    type Error = E;
+
+    fn transform(&self, input: I) -> Result<O, E> {
+        self.inner.transform(input).map_err(&self.f)
+    }
+}
+
+/// Transformer created by [`Transformer::or_else`].
+pub struct OrElse<A, B> {
+    first: A,
+    fallback: B,
+}
+
+impl<I: Clone, O, A, B> Transformer<I, O> for OrElse<A, B>
+where
+    A: Transformer<I, O>,
+    B: Transformer<I, O>,
+{
+    type Error = B::Error;
+
+    fn transform(&self, input: I) -> Result<O, B::Error> {
+        self.first
+            .transform(input.clone())
+            .or_else(|_| self.fallback.transform(input))
+    }
+}
+
+/// Transformer created by [`Transformer::optional`].
+pub struct Optional<T> {
+    inner: T,
+}
+
+impl<I, O, T: Transformer<I, O>> Transformer<Option<I>, Option<O>> for Optional<T> {
+    type Error = T::Error;
+
+    fn transform(&self, input: Option<I>) -> Result<Option<O>, T::Error> {
+        input.map(|input| self.inner.transform(input)).transpose()
+    }
+}
+
+/// Strings cleaned up by the string transformers, e.g. [`Trim`].
+///
+/// `Option`s and `Vec`s of strings are cleaned up item by item, so that the
+/// transformers apply to `Option<String>` fields as well.
+pub trait StringLike {
+    /// Apply `f` to every string of the value.
+    fn map_string(self, f: &mut impl FnMut(String) -> String) -> Self;
+}
+
+impl StringLike for String {
+    fn map_string(self, f: &mut impl FnMut(String) -> String) -> Self {
+        f(self)
+    }
+}
+
+impl<T: StringLike> StringLike for Option<T> {
+    fn map_string(self, f: &mut impl FnMut(String) -> String) -> Self {
+        self.map(|s| s.map_string(f))
+    }
+}
+
+impl<T: StringLike> StringLike for Vec<T> {
+    fn map_string(self, f: &mut impl FnMut(String) -> String) -> Self {
+        self.into_iter().map(|s| s.map_string(f)).collect()
+    }
+}
+
+/// Keep the string if the part returned by `f` is all of it, to avoid an
+/// allocation.
+fn keep_or_copy(s: String, f: impl Fn(&str) -> &str) -> String {
+    let part = f(&s);
+    if part.len() == s.len() {
+        s
+    } else {
+        part.to_owned()
+    }
+}
+
+fn trim(s: String) -> String {
+    keep_or_copy(s, str::trim)
+}
+
+fn trim_start(s: String) -> String {
+    keep_or_copy(s, str::trim_start)
+}
+
+fn trim_end(s: String) -> String {
+    keep_or_copy(s, str::trim_end)
+}
+
+fn lowercase(s: String) -> String {
+    s.to_lowercase()
+}
+
+fn uppercase(s: String) -> String {
+    s.to_uppercase()
+}
+
+fn collapse_whitespace(s: String) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut in_space = false;
+    for c in s.chars() {
+        if !c.is_whitespace() {
+            out.push(c);
+        } else if !in_space {
+            out.push(' ');
+        }
+        in_space = c.is_whitespace();
+    }
+    out
+}
+
+fn strip_control_chars(s: String) -> String {
+    let stripped = |c: char| c.is_control() && !matches!(c, '\n' | '\r' | '\t');
+    if s.chars().any(stripped) {
+        s.chars().filter(|&c| !stripped(c)).collect()
+    } else {
+        s
+    }
+}
+
+#[cfg(feature = "normalization")]
+fn nfc(s: String) -> String {
+    use unicode_normalization::{UnicodeNormalization, is_nfc};
+
+    if is_nfc(&s) { s } else { s.nfc().collect() }
+}
+
+#[cfg(feature = "normalization")]
+fn nfkc(s: String) -> String {
+    use unicode_normalization::{UnicodeNormalization, is_nfkc};
+
+    if is_nfkc(&s) { s } else { s.nfkc().collect() }
+}
+
+macro_rules! string_transformers {
+    ($($(#[$meta:meta])* $name:ident => $f:ident;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy)]
+            pub struct $name;
+
+            impl<S: StringLike> Transformer<S, S> for $name {
+                type Error = Infallible;
+
+                fn transform(&self, input: S) -> Result<S, Infallible> {
+                    Ok(input.map_string(&mut $f))
+                }
+            }
+        )*
+    };
+}
+
+string_transformers! {
+    /// Removes leading and trailing whitespace, like `trans(trim)`.
+    Trim => trim;
+    /// Removes leading whitespace, like `trans(trim_start)`.
+    TrimStart => trim_start;
+    /// Removes trailing whitespace, like `trans(trim_end)`.
+    TrimEnd => trim_end;
+    /// Converts a string to lowercase, like `trans(lowercase)`.
+    Lowercase => lowercase;
+    /// Converts a string to uppercase, like `trans(uppercase)`.
+    Uppercase => uppercase;
+    /// Replaces every run of whitespace with a single space, like
+    /// `trans(collapse_whitespace)`.
+    CollapseWhitespace => collapse_whitespace;
+    /// Removes control characters other than `\n`, `\r` and `\t`, like
+    /// `trans(strip_control_chars)`.
+    StripControlChars => strip_control_chars;
+}
+
+#[cfg(feature = "normalization")]
+string_transformers! {
+    /// Normalizes a string to the Unicode Normalization Form C, like
+    /// `trans(nfc)`.
+    Nfc => nfc;
+    /// Normalizes a string to the Unicode Normalization Form KC, like
+    /// `trans(nfkc)`.
+    Nfkc => nfkc;
+}
+
+/// Turns an empty string into `None`, like `trans(empty_to_none)`.
+///
+/// Whitespace is kept, so that `Trim.then(EmptyToNone)` is needed to also
+/// discard blank strings.
+#[derive(Debug, Clone, Copy)]
+pub struct EmptyToNone;
+
+impl Transformer<String, Option<String>> for EmptyToNone {
+    type Error = Infallible;
+
+    fn transform(&self, input: String) -> Result<Option<String>, Infallible> {
+        Ok(Some(input).filter(|s| !s.is_empty()))
+    }
+}
+
+/// Parses a string with [`FromStr`], see [`parse`].
+pub struct Parse<T> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Create a transformer parsing a string into a `T`.
+pub fn parse<T: FromStr>() -> Parse<T> {
+    Parse {
+        _marker: PhantomData,
+    }
+}
+
+impl<T: FromStr> Transformer<String, T> for Parse<T>
+where
+    T::Err: ErrorShow + 'static,
+{
+    type Error = T::Err;
+
+    fn transform(&self, input: String) -> Result<T, T::Err> {
+        input.parse()
+    }
+}
@@ -0,0 +1,578 @@
+//! Reusable validator objects.
+//!
+//! A [`Validator<T>`] checks a `&T` and describes its rule as a [`Violation`]:
+//! a machine-readable code, a message and the parameters of the rule. Unlike
+//! the closures and expressions passed to `#[valid(...)]`, validators can be
+//! named, shared across crates and combined without losing their error
+//! metadata.
+//!
+//! ```rust
+//! use valust::validator::{Ascii, Len, Validator};
+//!
+//! let rule = Len::max(5).and(Ascii);
+//! assert!(rule.check("hello").is_ok());
+//!
+//! let violation = rule.check("hello, world").unwrap_err();
+//! assert_eq!(violation.code, "len");
+//! assert_eq!(violation.params, [("max", "5".to_owned())]);
+//! ```
+//!
+//! The derive macro applies validators with `#[valid(with(...))]`, which
+//! reports a failure as a [`ValidateError`](crate::error::validate::ValidateError)
+//! carrying the code, message and parameters of the violation.
+
+use alloc::borrow::Cow;
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+
+/// A rule violated by a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Machine-readable code of the rule, e.g. `len`.
+    pub code: Cow<'static, str>,
+    /// Message of the error.
+    ///
+    /// `{label}` is replaced with the label of the field, and `{<name>}` with
+    /// the parameter `name`.
+    pub message: Cow<'static, str>,
+    /// Parameters of the rule, `Debug`-formatted.
+    pub params: Vec<(&'static str, String)>,
+}
+
+impl Violation {
+    /// Create a violation without parameters.
+    pub fn new(
+        code: impl Into<Cow<'static, str>>,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+            params: Vec::new(),
+        }
+    }
+
+    /// Add a parameter.
+    pub fn with_param(mut self, name: &'static str, value: impl Debug) -> Self {
+        self.params.push((name, format!("{:?}", value)));
+        self
+    }
+}
+
+/// A reusable validator of `T` values.
+///
+/// Only [`is_valid`](Validator::is_valid) and
+/// [`violation`](Validator::violation) are required. The combinators take the
+/// checked type as a parameter, so that it is inferred where the combined
+/// validator is used.
+pub trait Validator<T: ?Sized> {
+    /// Whether the value passes the rule.
+    fn is_valid(&self, value: &T) -> bool;
+
+    /// Describe the rule, which is reported when a value fails it.
+    fn violation(&self) -> Violation;
+
+    /// Describe the opposite rule, reported by [`Validator::not`].
+    ///
+    /// Defaults to the violation with the code `not_<code>` and the first
+    /// `must` (or `must not`) of its message negated. Combinators override it
+    /// to negate each of their rules.
+    fn negated_violation(&self) -> Violation {
+        let violation = self.violation();
+        Violation {
+            code: Cow::Owned(format!("not_{}", violation.code)),
+            message: Cow::Owned(negate(&violation.message)),
+            params: violation.params,
+        }
+    }
+
+    /// Check the value, returning the violated rule if it fails.
+    fn check(&self, value: &T) -> Result<(), Violation> {
+        if self.is_valid(value) {
+            Ok(())
+        } else {
+            Err(self.violation())
+        }
+    }
+
+    /// Require both rules, reporting the first one failing.
+    fn and<V: Validator<T>>(self, other: V) -> And<Self, V, T>
+    where
+        Self: Sized,
+    {
+        And {
+            first: self,
+            second: other,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Require either rule.
+    fn or<V: Validator<T>>(self, other: V) -> Or<Self, V, T>
+    where
+        Self: Sized,
+    {
+        Or {
+            first: self,
+            second: other,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Require the value to fail the rule.
+    fn not(self) -> Not<Self, T>
+    where
+        Self: Sized,
+    {
+        Not {
+            inner: self,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Apply the rule to every item of a `Vec<T>` or `[T]`.
+    fn each(self) -> Each<Self, T>
+    where
+        Self: Sized,
+        T: Sized,
+    {
+        Each {
+            inner: self,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized, V: Validator<T> + ?Sized> Validator<T> for &V {
+    fn is_valid(&self, value: &T) -> bool {
+        (**self).is_valid(value)
+    }
+
+    fn violation(&self) -> Violation {
+        (**self).violation()
+    }
+
+    fn negated_violation(&self) -> Violation {
+        (**self).negated_violation()
+    }
+
+    fn check(&self, value: &T) -> Result<(), Violation> {
+        (**self).check(value)
+    }
+}
+
+/// Validator created by [`Validator::and`].
+pub struct And<A, B, T: ?Sized> {
+    first: A,
+    second: B,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized, A: Validator<T>, B: Validator<T>> Validator<T> for And<A, B, T> {
+    fn is_valid(&self, value: &T) -> bool {
+        self.first.is_valid(value) && self.second.is_valid(value)
+    }
+
+    /// Both rules, with the code `<first>_and_<second>`.
+    fn violation(&self) -> Violation {
+        let (first, second) = (self.first.violation(), self.second.violation());
+        join(first, second, "and")
+    }
+
+    /// Either negated rule.
+    fn negated_violation(&self) -> Violation {
+        let (first, second) = (
+            self.first.negated_violation(),
+            self.second.negated_violation(),
+        );
+        join(first, second, "or")
+    }
+
+    fn check(&self, value: &T) -> Result<(), Violation> {
+        self.first.check(value)?;
+        self.second.check(value)
+    }
+}
+
+/// Validator created by [`Validator::or`].
+pub struct Or<A, B, T: ?Sized> {
+    first: A,
+    second: B,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized, A: Validator<T>, B: Validator<T>> Validator<T> for Or<A, B, T> {
+    fn is_valid(&self, value: &T) -> bool {
+        self.first.is_valid(value) || self.second.is_valid(value)
+    }
+
+    /// Both rules, with the code `<first>_or_<second>`.
+    fn violation(&self) -> Violation {
+        let (first, second) = (self.first.violation(), self.second.violation());
+        join(first, second, "or")
+    }
+
+    /// Both negated rules.
+    fn negated_violation(&self) -> Violation {
+        let (first, second) = (
+            self.first.negated_violation(),
+            self.second.negated_violation(),
+        );
+        join(first, second, "and")
+    }
+}
+
+/// Join two rules with `and` or `or`, in their codes and messages.
+fn join(first: Violation, second: Violation, conjunction: &str) -> Violation {
+    Violation {
+        code: Cow::Owned(format!("{}_{}_{}", first.code, conjunction, second.code)),
+        message: Cow::Owned(format!(
+            "{}, {} {}",
+            first.message, conjunction, second.message
+        )),
+        params: first.params.into_iter().chain(second.params).collect(),
+    }
+}
+
+/// Validator created by [`Validator::not`].
+pub struct Not<V, T: ?Sized> {
+    inner: V,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized, V: Validator<T>> Validator<T> for Not<V, T> {
+    fn is_valid(&self, value: &T) -> bool {
+        !self.inner.is_valid(value)
+    }
+
+    /// The negated inner rule.
+    fn violation(&self) -> Violation {
+        self.inner.negated_violation()
+    }
+
+    /// The inner rule.
+    fn negated_violation(&self) -> Violation {
+        self.inner.violation()
+    }
+}
+
+/// Negate a message in the form of `{label} must ...`.
+fn negate(message: &str) -> String {
+    if let Some((head, tail)) = message.split_once(" must not ") {
+        format!("{} must {}", head, tail)
+    } else if let Some((head, tail)) = message.split_once(" must ") {
+        format!("{} must not {}", head, tail)
+    } else {
+        format!("{{label}} must not pass: {}", message)
+    }
+}
+
+/// Validator created by [`Validator::each`].
+pub struct Each<V, T> {
+    inner: V,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T, V: Validator<T>> Validator<[T]> for Each<V, T> {
+    fn is_valid(&self, value: &[T]) -> bool {
+        value.iter().all(|item| self.inner.is_valid(item))
+    }
+
+    /// The rule of the items.
+    fn violation(&self) -> Violation {
+        self.inner.violation()
+    }
+
+    /// Check every item, reporting the first failing one with an `index`
+    /// parameter.
+    fn check(&self, value: &[T]) -> Result<(), Violation> {
+        value.iter().enumerate().try_for_each(|(index, item)| {
+            self.inner.check(item).map_err(|violation| {
+                let message = format!("{} (item {{index}})", violation.message);
+                Violation {
+                    message: Cow::Owned(message),
+                    ..violation
+                }
+                .with_param("index", index)
+            })
+        })
+    }
+}
+
+impl<T, V: Validator<T>> Validator<Vec<T>> for Each<V, T> {
+    fn is_valid(&self, value: &Vec<T>) -> bool {
+        Validator::<[T]>::is_valid(self, value)
+    }
+
+    fn violation(&self) -> Violation {
+        Validator::<[T]>::violation(self)
+    }
+
+    fn check(&self, value: &Vec<T>) -> Result<(), Violation> {
+        Validator::<[T]>::check(self, value)
+    }
+}
+
+/// Validator from a closure, see [`predicate`].
+pub struct Predicate<F> {
+    code: &'static str,
+    message: &'static str,
+    f: F,
+}
+
+/// Create a validator from a closure, with the code and message of its rule.
+///
+/// ```rust
+/// use valust::validator::{Validator, predicate};
+///
+/// let even = predicate("even", "{label} must be even", |x: &i32| x % 2 == 0);
+/// assert!(even.check(&4).is_ok());
+/// assert_eq!(even.check(&3).unwrap_err().code, "even");
+/// ```
+pub fn predicate<T: ?Sized, F: Fn(&T) -> bool>(
+    code: &'static str,
+    message: &'static str,
+    f: F,
+) -> Predicate<F> {
+    Predicate { code, message, f }
+}
+
+impl<T: ?Sized, F: Fn(&T) -> bool> Validator<T> for Predicate<F> {
+    fn is_valid(&self, value: &T) -> bool {
+        (self.f)(value)
+    }
+
+    fn violation(&self) -> Violation {
+        Violation::new(self.code, self.message)
+    }
+}
+
+/// Types with a length, for [`Len`] and [`NonEmpty`].
+///
+/// The length of strings is their number of bytes.
+pub trait Length {
+    /// Length of the value.
+    fn length(&self) -> usize;
+}
+
+impl<T: Length + ?Sized> Length for &T {
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+impl Length for str {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for VecDeque<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for BTreeSet<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> Length for BTreeMap<K, V> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> Length for std::collections::HashSet<T, S> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Length for std::collections::HashMap<K, V, S> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Requires the [`Length`] of the value to be in bounds, like `len(...)`.
+#[derive(Debug, Clone, Copy)]
+pub struct Len {
+    min: Option<usize>,
+    max: Option<usize>,
+}
+
+impl Len {
+    /// Require a length of at least `min`.
+    pub const fn min(min: usize) -> Self {
+        Self {
+            min: Some(min),
+            max: None,
+        }
+    }
+
+    /// Require a length of at most `max`.
+    pub const fn max(max: usize) -> Self {
+        Self {
+            min: None,
+            max: Some(max),
+        }
+    }
+
+    /// Require a length between `min` and `max`, inclusive.
+    pub const fn between(min: usize, max: usize) -> Self {
+        Self {
+            min: Some(min),
+            max: Some(max),
+        }
+    }
+
+    /// Require a length of exactly `len`.
+    pub const fn exactly(len: usize) -> Self {
+        Self::between(len, len)
+    }
+}
+
+impl<T: Length + ?Sized> Validator<T> for Len {
+    fn is_valid(&self, value: &T) -> bool {
+        let len = value.length();
+        self.min.map_or(true, |min| len >= min)
+            && self.max.map_or(true, |max| len <= max)
+    }
+
+    fn violation(&self) -> Violation {
+        let (message, params): (&str, &[_]) = match (self.min, self.max) {
+            (Some(min), Some(max)) if min == max => {
+                ("{label} must have a length of {len}", &[("len", min)])
+            }
+            (Some(min), Some(max)) => (
+                "{label} must have a length between {min} and {max}",
+                &[("min", min), ("max", max)],
+            ),
+            (Some(min), None) => (
+                "{label} must have a length of at least {min}",
+                &[("min", min)],
+            ),
+            (None, Some(max)) => (
+                "{label} must have a length of at most {max}",
+                &[("max", max)],
+            ),
+            (None, None) => ("{label} must have a length", &[]),
+        };
+        params
+            .iter()
+            .fold(Violation::new("len", message), |v, &(name, value)| {
+                v.with_param(name, value)
+            })
+    }
+}
+
+/// Requires the value to have a non-zero [`Length`], like `non_empty`.
+#[derive(Debug, Clone, Copy)]
+pub struct NonEmpty;
+
+impl<T: Length + ?Sized> Validator<T> for NonEmpty {
+    fn is_valid(&self, value: &T) -> bool {
+        value.length() != 0
+    }
+
+    fn violation(&self) -> Violation {
+        Violation::new("non_empty", "{label} must not be empty")
+    }
+}
+
+/// Requires a string to only contain ASCII characters, like `ascii`.
+#[derive(Debug, Clone, Copy)]
+pub struct Ascii;
+
+impl<T: AsRef<str> + ?Sized> Validator<T> for Ascii {
+    fn is_valid(&self, value: &T) -> bool {
+        value.as_ref().is_ascii()
+    }
+
+    fn violation(&self) -> Violation {
+        Violation::new("ascii", "{label} must only contain ASCII characters")
+    }
+}
+
+/// Requires the value to be in bounds, like `range(...)`.
+#[derive(Debug, Clone, Copy)]
+pub struct Range<B> {
+    min: Option<B>,
+    max: Option<B>,
+}
+
+impl<B> Range<B> {
+    /// Require a value of at least `min`.
+    pub const fn min(min: B) -> Self {
+        Self {
+            min: Some(min),
+            max: None,
+        }
+    }
+
+    /// Require a value of at most `max`.
+    pub const fn max(max: B) -> Self {
+        Self {
+            min: None,
+            max: Some(max),
+        }
+    }
+
+    /// Require a value between `min` and `max`, inclusive.
+    pub const fn between(min: B, max: B) -> Self {
+        Self {
+            min: Some(min),
+            max: Some(max),
+        }
+    }
+}
+
+impl<T: PartialOrd<B> + ?Sized, B: Debug> Validator<T> for Range<B> {
+    fn is_valid(&self, value: &T) -> bool {
+        self.min.as_ref().map_or(true, |min| value >= min)
+            && self.max.as_ref().map_or(true, |max| value <= max)
+    }
+
+    fn violation(&self) -> Violation {
+        let message = match (&self.min, &self.max) {
+            (Some(_), Some(_)) => "{label} must be between {min} and {max}",
+            (Some(_), None) => "{label} must be at least {min}",
+            (None, _) => "{label} must be at most {max}",
+        };
+        let mut violation = Violation::new("range", message);
+        if let Some(min) = &self.min {
+            violation = violation.with_param("min", min);
+        }
+        if let Some(max) = &self.max {
+            violation = violation.with_param("max", max);
+        }
+        violation
+    }
+}
@@ -0,0 +1,109 @@
+//! Success-path cost of fallible transformers.
+//!
+//! `try(...)` used to clone its input beforehand so that it could be displayed
+//! on failure. It now formats the input into an inline buffer instead, while
+//! `try_ret(...)` gets the input handed back and only formats it on failure.
+
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use valust::Validate;
+use valust_derive::Valust;
+use valust_utils::convert::by_ref;
+
+/// The former codegen of `try(...)`: clone the input, run the transformer,
+/// and only format the clone on failure.
+fn cloned<I: Clone + Debug, T, E>(
+    f: impl Fn(I) -> Result<T, E>,
+) -> impl Fn(I) -> Result<T, E> {
+    move |input| {
+        let clone = input.clone();
+        let result = f(input);
+        if result.is_err() {
+            black_box(format!("{:?}", clone));
+        }
+        black_box(clone);
+        result
+    }
+}
+
+fn parse(s: String) -> Result<u64, std::num::ParseIntError> {
+    s.parse()
+}
+
+fn parse_all(v: Vec<String>) -> Result<Vec<u64>, std::num::ParseIntError> {
+    v.iter().map(|s| s.parse()).collect()
+}
+
+#[derive(Debug, Valust)]
+#[rename(RawCloned)]
+#[valust(value(none))]
+struct Cloned {
+    #[trans(func(String => try(cloned(parse))))]
+    a: u64,
+    #[trans(func(String => try(cloned(parse))))]
+    b: u64,
+    #[trans(func(Vec<String> => try(cloned(parse_all))))]
+    c: Vec<u64>,
+}
+
+#[derive(Debug, Valust)]
+#[rename(RawCaptured)]
+struct Captured {
+    #[trans(func(String => try(parse)))]
+    a: u64,
+    #[trans(func(String => try(parse)))]
+    b: u64,
+    #[trans(func(Vec<String> => try(parse_all)))]
+    c: Vec<u64>,
+}
+
+#[derive(Debug, Valust)]
+#[rename(RawReturned)]
+struct Returned {
+    #[trans(func(String => try_ret(by_ref(|s: &String| s.parse::<u64>()))))]
+    a: u64,
+    #[trans(func(String => try_ret(by_ref(|s: &String| s.parse::<u64>()))))]
+    b: u64,
+    #[trans(func(Vec<String> => try_ret(by_ref(|v: &Vec<String>| v.iter().map(|s| s.parse::<u64>()).collect::<Result<Vec<_>, _>>()))))]
+    c: Vec<u64>,
+}
+
+fn input() -> (String, String, Vec<String>) {
+    (
+        "12345".to_owned(),
+        "67890".to_owned(),
+        (0..64).map(|i| i.to_string()).collect(),
+    )
+}
+
+fn bench_transform(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fallible transform (success path)");
+    group.bench_function("try (old codegen, clone input)", |b| {
+        b.iter_batched(
+            input,
+            |(a, b, c)| Cloned::validate(black_box(RawCloned { a, b, c })).unwrap(),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("try (inline snapshot)", |b| {
+        b.iter_batched(
+            input,
+            |(a, b, c)| Captured::validate(black_box(RawCaptured { a, b, c })).unwrap(),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("try_ret (input handed back)", |b| {
+        b.iter_batched(
+            input,
+            |(a, b, c)| Returned::validate(black_box(RawReturned { a, b, c })).unwrap(),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_transform);
+criterion_main!(benches);
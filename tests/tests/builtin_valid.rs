@@ -0,0 +1,95 @@
+#![allow(dead_code)]
+
+use valust::Validate;
+use valust::error::ValidationError;
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+pub struct Signup {
+    #[valid(len(min = 3, max = 16))]
+    username: String,
+    #[valid(range(min = 13, max = 130))]
+    age: u8,
+    #[valid(range(min = 0.0, max = 1.0, exclusive))]
+    ratio: f64,
+    #[valid(non_empty, len(max = 3))]
+    tags: Vec<String>,
+    #[valid(contains('@'), ends_with(".org"))]
+    email: String,
+    #[valid(starts_with("+"), len(eq = 12))]
+    phone: String,
+}
+
+fn valid() -> RawSignup {
+    RawSignup {
+        username: "ada".to_owned(),
+        age: 36,
+        ratio: 0.5,
+        tags: vec!["math".to_owned()],
+        email: "ada@example.org".to_owned(),
+        phone: "+33612345678".to_owned(),
+    }
+}
+
+fn messages(err: &ValidationError) -> Vec<(&str, &str)> {
+    err.validates
+        .iter()
+        .map(|e| (e.path.as_str(), e.message.as_deref().unwrap()))
+        .collect()
+}
+
+#[test]
+fn test_builtin_valid() {
+    assert!(Signup::validate(valid()).is_ok());
+}
+
+#[test]
+fn test_builtin_valid_messages() {
+    let err = Signup::validate(RawSignup {
+        username: "a".repeat(20),
+        age: 8,
+        ratio: 1.0,
+        tags: vec![],
+        email: "ada.example.com".to_owned(),
+        phone: "0612345678".to_owned(),
+    })
+    .unwrap_err();
+
+    assert_eq!(
+        messages(&err),
+        [
+            ("username", "`username` must have a length between 3 and 16"),
+            ("age", "`age` must be between 13 and 130"),
+            ("ratio", "`ratio` must be at least 0.0 and less than 1.0"),
+            ("tags", "`tags` must not be empty"),
+            ("email", "`email` must contain '@'"),
+            ("phone", "`phone` must start with \"+\""),
+        ]
+    );
+}
+
+#[test]
+fn test_builtin_valid_params() {
+    let err = Signup::validate(RawSignup {
+        tags: vec!["a".to_owned(); 4],
+        ..valid()
+    })
+    .unwrap_err();
+
+    let tags = &err.validates[0];
+    assert_eq!(tags.expression, "len(max = 3)");
+    assert_eq!(tags.params, [("max", "3".to_owned())]);
+    assert_eq!(tags.message.as_deref(), Some("`tags` must have a length of at most 3"));
+
+    let err = Signup::validate(RawSignup {
+        age: 200,
+        ..valid()
+    })
+    .unwrap_err();
+    let age = &err.validates[0];
+    assert_eq!(age.expression, "range(13..=130)");
+    assert_eq!(
+        age.params,
+        [("min", "13".to_owned()), ("max", "130".to_owned())]
+    );
+}
@@ -0,0 +1,98 @@
+#![allow(dead_code)]
+
+use valust::Validate;
+use valust::captures::FromCaptures;
+use valust_derive::{FromCaptures, Valust};
+
+#[derive(Debug, PartialEq, FromCaptures)]
+pub struct Version {
+    major: u8,
+    minor: u8,
+    patch: Option<u8>,
+}
+
+#[test]
+fn test_captures() {
+    #[derive(Debug, Valust)]
+    pub struct Release {
+        #[trans(captures(r"^v(?<major>\w+)\.(?<minor>\w+)(?:\.(?<patch>\w+))?$" => Version))]
+        version: Version,
+    }
+
+    let raw = |version: &str| RawRelease {
+        version: version.to_owned(),
+    };
+
+    let release = Release::validate(raw("v1.2")).unwrap();
+    assert_eq!(
+        release.version,
+        Version {
+            major: 1,
+            minor: 2,
+            patch: None
+        }
+    );
+    let release = Release::validate(raw("v1.2.3")).unwrap();
+    assert_eq!(release.version.patch, Some(3));
+
+    let err = Release::validate(raw("1.2")).unwrap_err();
+    assert_eq!(err.transforms.len(), 1);
+    let err = &err.transforms[0];
+    assert_eq!(err.path, "version");
+    assert_eq!(err.value, "(String) \"1.2\"");
+    assert_eq!(
+        err.message.as_deref(),
+        Some("`version` does not match the regex")
+    );
+    assert_eq!(err.source_type_name, "String");
+    assert_eq!(err.target_type_name, "Version");
+
+    // one error per failing group
+    let err = Release::validate(raw("v1.x.300")).unwrap_err();
+    assert_eq!(err.transforms.len(), 2);
+    let (minor, patch) = (&err.transforms[0], &err.transforms[1]);
+    assert_eq!(minor.path, "version.minor");
+    assert_eq!(minor.value, "(&str) \"x\"");
+    assert_eq!(minor.target_type_name, "u8");
+    assert_eq!(minor.message, None);
+    assert_eq!(patch.path, "version.patch");
+    assert_eq!(
+        patch.cause.to_string(),
+        "number too large to fit in target type"
+    );
+}
+
+#[test]
+fn test_captures_groups() {
+    // checked against literal regexes at compile time
+    assert_eq!(
+        <Version as FromCaptures>::GROUPS,
+        ["major", "minor", "patch"]
+    );
+}
+
+#[test]
+fn test_captures_runtime_regex() {
+    fn broken() -> &'static str {
+        "(?<major>"
+    }
+
+    #[derive(Debug, Valust)]
+    pub struct Broken {
+        #[trans(captures(broken() => Version, "invalid version"))]
+        version: Version,
+    }
+
+    let err = Broken::validate(RawBroken {
+        version: "v1.2".to_owned(),
+    })
+    .unwrap_err();
+    assert_eq!(
+        err.transforms[0].message.as_deref(),
+        Some("invalid version")
+    );
+    assert_eq!(
+        err.transforms[0].expression,
+        "captures(broken() => Version)"
+    );
+}
@@ -0,0 +1,73 @@
+#![allow(dead_code)]
+
+use std::marker::PhantomData;
+
+use valust::Validate;
+use valust_derive::Valust;
+
+fn slugify(title: &str) -> String {
+    title
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[derive(Debug, Valust)]
+#[post(slug.len() <= 24)]
+pub struct Article {
+    #[trans(expr(String => title.trim().to_owned()))]
+    #[valid(expr(!title.is_empty()))]
+    title: String,
+    #[valust(computed = slugify(&title))]
+    slug: String,
+    #[valust(skip)]
+    views: u64,
+    #[valust(skip, default = vec!["draft".to_owned()])]
+    tags: Vec<String>,
+    #[valust(skip)]
+    marker: PhantomData<u8>,
+}
+
+#[test]
+fn test_computed() {
+    let article = Article::validate(RawArticle {
+        title: "  Hello Valust World ".to_owned(),
+    })
+    .unwrap();
+    assert_eq!(article.title, "Hello Valust World");
+    assert_eq!(article.slug, "hello-valust-world");
+    assert_eq!(article.views, 0);
+    assert_eq!(article.tags, ["draft"]);
+}
+
+#[test]
+fn test_computed_failed() {
+    let err = Article::validate(RawArticle {
+        title: "   ".to_owned(),
+    })
+    .unwrap_err();
+    assert_eq!(err.validates.len(), 1);
+    assert_eq!(err.validates[0].field, "title");
+
+    // computed fields are checked by `post`
+    let err = Article::validate(RawArticle {
+        title: "A rather long title for once".to_owned(),
+    })
+    .unwrap_err();
+    assert_eq!(err.validates.len(), 1);
+}
+
+#[derive(Debug, Valust)]
+pub struct Pair(
+    #[valid(expr(_0 > 0))] i32,
+    #[valust(computed = _0 * 2)] i32,
+    #[valust(skip, default = "pair")] &'static str,
+);
+
+#[test]
+fn test_computed_unnamed() {
+    let pair = Pair::validate(RawPair(21)).unwrap();
+    assert_eq!((pair.0, pair.1, pair.2), (21, 42, "pair"));
+    assert!(Pair::validate(RawPair(0)).is_err());
+}
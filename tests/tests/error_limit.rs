@@ -0,0 +1,75 @@
+#![allow(dead_code)]
+
+use valust::Validate;
+use valust::error::display::ErrorDisplay;
+use valust::error::set_max_errors;
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+#[forward_derive(Debug)]
+pub struct Item {
+    #[valid(expr(a > 0))]
+    a: i32,
+    #[valid(expr(b > 0))]
+    b: i32,
+}
+
+#[derive(Debug, Valust)]
+#[forward_derive(Debug)]
+pub struct Order {
+    #[forward]
+    first: Item,
+    #[forward]
+    second: Item,
+    #[valid(expr(!note.is_empty()))]
+    note: String,
+}
+
+fn bad_order() -> RawOrder {
+    RawOrder {
+        first: RawItem { a: 0, b: 0 },
+        second: RawItem { a: 0, b: 0 },
+        note: String::new(),
+    }
+}
+
+#[test]
+fn test_fail_fast() {
+    #[derive(Debug, Valust)]
+    #[valust(fail_fast)]
+    pub struct Data {
+        #[valid(expr(a > 0))]
+        a: i32,
+        #[trans(func(String => try(|s: String| s.parse::<i32>())))]
+        b: i32,
+    }
+
+    let err = Data::validate(RawData {
+        a: 0,
+        b: "x".to_owned(),
+    })
+    .unwrap_err();
+    assert_eq!(err.len(), 1);
+    assert_eq!(err.validates[0].path, "a");
+    assert!(!err.truncated);
+}
+
+#[test]
+fn test_max_errors() {
+    let err = Order::validate(bad_order()).unwrap_err();
+    assert_eq!(err.len(), 5);
+    assert!(!err.truncated);
+
+    set_max_errors(Some(3));
+    let err = Order::validate(bad_order()).unwrap_err();
+    set_max_errors(None);
+
+    // `second.b` is dropped, and `note` is not validated at all
+    let paths: Vec<_> = err.validates.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, ["first.a", "first.b", "second.a"]);
+    assert!(err.truncated);
+    assert!(
+        err.human_readable_stringify()
+            .ends_with("... and more errors were omitted.\n")
+    );
+}
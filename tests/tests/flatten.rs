@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+
+use serde::Deserialize;
+use valust::{Raw, Validate};
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+#[forward_derive(Deserialize)]
+pub struct Pagination {
+    #[valid(expr(page > 0))]
+    page: u32,
+    #[valid(expr((1..=100).contains(&per_page)))]
+    per_page: u32,
+}
+
+#[derive(Debug, Valust)]
+#[forward_derive(Deserialize)]
+pub struct Audit {
+    #[valid(expr(!by.is_empty()))]
+    by: String,
+}
+
+#[derive(Debug, Valust)]
+#[forward_derive(Deserialize)]
+pub struct ListUsers {
+    #[valid(expr(query.len() < 32))]
+    query: String,
+    #[forward(flatten)]
+    pagination: Pagination,
+    #[forward(flatten, Audit)]
+    audit: Audit,
+}
+
+#[test]
+fn test_flatten() {
+    let text = r#"{ "query": "ada", "page": 2, "per_page": 50, "by": "admin" }"#;
+    let raw: Raw<ListUsers> = serde_json::from_str(text).unwrap();
+    let list = ListUsers::validate(raw).unwrap();
+    assert_eq!(list.pagination.page, 2);
+    assert_eq!(list.audit.by, "admin");
+}
+
+#[test]
+fn test_flatten_paths() {
+    let text = r#"{ "query": "ada", "page": 0, "per_page": 500, "by": "" }"#;
+    let raw: Raw<ListUsers> = serde_json::from_str(text).unwrap();
+    let err = ListUsers::validate(raw).unwrap_err();
+
+    let paths: Vec<_> = err.validates.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, ["page", "per_page", "by"]);
+}
+
+#[test]
+fn test_flatten_without_serde() {
+    #[derive(Debug, Valust)]
+    pub struct Range {
+        #[valid(expr(start <= 10))]
+        start: u32,
+    }
+
+    #[derive(Debug, Valust)]
+    pub struct Query {
+        #[forward(flatten)]
+        range: Range,
+    }
+
+    let err = Query::validate(RawQuery {
+        range: RawRange { start: 11 },
+    })
+    .unwrap_err();
+    assert_eq!(err.validates[0].path, "start");
+}
@@ -0,0 +1,64 @@
+#![allow(dead_code)]
+
+use std::borrow::Cow;
+
+use valust::Validate;
+use valust::error::label::set_label_hook;
+use valust_derive::Valust;
+
+fn german(field: &str, _label: &str) -> Option<Cow<'static, str>> {
+    match field {
+        "user_email" => Some("E-Mail-Adresse".into()),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_label() {
+    #[derive(Debug, Valust)]
+    pub struct Account {
+        #[valust(label = "E-mail address")]
+        #[valid(email)]
+        user_email: String,
+        #[valid(expr(age >= 18))]
+        age: u8,
+        #[valust(label = "Nickname")]
+        #[valid(expr(!nickname.is_empty(), "{label} must not be empty"))]
+        nickname: String,
+    }
+
+    fn raw() -> RawAccount {
+        RawAccount {
+            user_email: "nope".to_owned(),
+            age: 1,
+            nickname: String::new(),
+        }
+    }
+
+    let err = Account::validate(raw()).unwrap_err();
+    let labels: Vec<_> = err.validates.iter().map(|e| e.label.as_ref()).collect();
+    assert_eq!(labels, ["E-mail address", "age", "Nickname"]);
+    let messages: Vec<_> = err
+        .validates
+        .iter()
+        .map(|e| e.message.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "E-mail address is not a valid email address",
+            "`age`'s validator expression evaluate to `false`",
+            "Nickname must not be empty",
+        ]
+    );
+
+    set_label_hook(Some(german));
+    let err = Account::validate(raw()).unwrap_err();
+    set_label_hook(None);
+    assert_eq!(err.validates[0].label, "E-Mail-Adresse");
+    assert_eq!(
+        err.validates[0].message.as_deref(),
+        Some("E-Mail-Adresse is not a valid email address")
+    );
+    assert_eq!(err.validates[1].label, "age");
+}
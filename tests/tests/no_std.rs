@@ -0,0 +1,58 @@
+//! The derive output must not rely on the `std` prelude or `::std` paths.
+#![no_std]
+#![allow(dead_code)]
+
+extern crate alloc;
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use valust::Validate;
+use valust_derive::Valust;
+
+#[derive(Valust)]
+#[pre(name.len() < 64)]
+#[post(*port > 0)]
+pub struct Device {
+    #[trans(func(String => try(|s: String| s.parse::<u16>())))]
+    port: u16,
+    #[valid(expr(!name.is_empty(), "name must not be empty"))]
+    name: String,
+    #[valust(value(max_items = 2))]
+    #[valid(expr(tags.len() < 3))]
+    tags: Vec<u8>,
+    #[forward]
+    inner: Inner,
+    untouched: u8,
+}
+
+#[derive(Valust)]
+pub struct Inner(#[valid(expr(_0 > 1))] u8);
+
+#[test]
+fn test_no_std() {
+    let ok = Device::validate(RawDevice {
+        port: "8080".to_owned(),
+        name: "gw".to_owned(),
+        tags: vec![1],
+        inner: RawInner(2),
+        untouched: 0,
+    });
+    assert!(ok.is_ok());
+
+    let err = Device::validate(RawDevice {
+        port: "x".to_owned(),
+        name: String::new(),
+        tags: vec![1, 2, 3, 4],
+        inner: RawInner(0),
+        untouched: 0,
+    })
+    .map(|_| ())
+    .unwrap_err();
+    assert_eq!(err.transforms.len(), 1);
+    assert_eq!(err.validates.len(), 3);
+    assert_eq!(err.validates[1].value, "(Vec < u8 >) [1, 2, …] (len = 4)");
+    assert_eq!(err.validates[2].path, "inner.0");
+}
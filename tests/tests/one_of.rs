@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+
+use valust::Validate;
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+pub struct Post {
+    #[valid(one_of("draft", "published", "archived"))]
+    status: String,
+    #[valid(not_one_of("admin", "root"))]
+    author: String,
+    #[valid(matches(Some(1..=5)))]
+    rating: Option<u8>,
+    #[valid(matches('a'..='z' | '_'))]
+    prefix: char,
+}
+
+fn valid() -> RawPost {
+    RawPost {
+        status: "draft".to_owned(),
+        author: "ada".to_owned(),
+        rating: Some(5),
+        prefix: '_',
+    }
+}
+
+#[test]
+fn test_one_of() {
+    assert!(Post::validate(valid()).is_ok());
+
+    let err = Post::validate(RawPost {
+        status: "deleted".to_owned(),
+        author: "root".to_owned(),
+        rating: None,
+        prefix: 'A',
+    })
+    .unwrap_err();
+    let messages: Vec<_> = err
+        .validates
+        .iter()
+        .map(|e| e.message.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            r#"`status` must be one of ["draft", "published", "archived"]"#,
+            r#"`author` must not be one of ["admin", "root"]"#,
+            "`rating` must match `Some(1 ..= 5)`",
+            "`prefix` must match `'a' ..= 'z' | '_'`",
+        ]
+    );
+    assert_eq!(
+        err.validates[0].params,
+        [("allowed", r#"["draft", "published", "archived"]"#.to_owned())]
+    );
+    assert_eq!(err.validates[0].expression, r#"one_of("draft", "published", "archived")"#);
+}
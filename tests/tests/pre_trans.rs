@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+
+use valust::Validate;
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+#[pre_trans({
+    if first.is_none() && last.is_none() {
+        if let Some((f, l)) = full_name.split_once(' ') {
+            *first = Some(f.to_owned());
+            *last = Some(l.to_owned());
+        }
+    }
+})]
+#[pre_trans((!full_name.is_empty(), "full name must not be empty"))]
+#[pre_trans(normalize_phone(country, phone))]
+#[valust(all_or_none(first, last))]
+pub struct Person {
+    full_name: String,
+    first: Option<String>,
+    last: Option<String>,
+    country: String,
+    #[valid(expr(phone.starts_with('+')))]
+    phone: String,
+}
+
+fn normalize_phone(country: &str, phone: &mut String) -> Result<(), String> {
+    let digits: String = phone.chars().filter(|c| c.is_ascii_digit()).collect();
+    match country {
+        "US" => *phone = format!("+1{}", digits),
+        "FR" => *phone = format!("+33{}", digits.trim_start_matches('0')),
+        _ => return Err(format!("unsupported country `{}`", country)),
+    }
+    Ok(())
+}
+
+fn raw(full_name: &str, country: &str) -> RawPerson {
+    RawPerson {
+        full_name: full_name.to_owned(),
+        first: None,
+        last: None,
+        country: country.to_owned(),
+        phone: "06 12 34 56 78".to_owned(),
+    }
+}
+
+#[test]
+fn test_pre_trans() {
+    let person = Person::validate(raw("Ada Lovelace", "FR")).unwrap();
+    assert_eq!(person.first.as_deref(), Some("Ada"));
+    assert_eq!(person.last.as_deref(), Some("Lovelace"));
+    assert_eq!(person.phone, "+33612345678");
+}
+
+#[test]
+fn test_pre_trans_error() {
+    // like `pre`, the first failing expression stops the checks
+    let err = Person::validate(raw("", "DE")).unwrap_err();
+    assert_eq!(err.len(), 1);
+    assert_eq!(err.validates[0].path, "<meta>");
+    assert_eq!(
+        err.validates[0].message.as_deref(),
+        Some("full name must not be empty")
+    );
+
+    let err = Person::validate(raw("Ada Lovelace", "DE")).unwrap_err();
+    assert_eq!(err.len(), 1);
+    assert_eq!(
+        err.validates[0].cause.as_ref().unwrap().to_string(),
+        "unsupported country `DE`"
+    );
+}
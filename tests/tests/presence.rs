@@ -0,0 +1,129 @@
+#![allow(dead_code)]
+
+use std::borrow::Cow;
+
+use valust::Validate;
+use valust::error::label::set_label_hook;
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+#[valust(exactly_one_of(email, phone, user_id))]
+#[valust(mutually_exclusive(coupon, gift_card))]
+#[valust(all_or_none(street, city))]
+pub struct Contact {
+    email: Option<String>,
+    phone: Option<String>,
+    user_id: Option<u32>,
+    coupon: Option<String>,
+    gift_card: Option<String>,
+    street: Option<String>,
+    city: Option<String>,
+}
+
+#[derive(Debug, Valust)]
+#[valust(at_least_one_of(a, b))]
+pub struct AtLeast {
+    a: Option<u8>,
+    b: Option<u8>,
+}
+
+fn paths(err: &valust::error::ValidationError) -> Vec<&str> {
+    err.validates.iter().map(|e| e.path.as_str()).collect()
+}
+
+#[test]
+fn test_presence_ok() {
+    let ok = Contact::validate(RawContact {
+        email: Some("a@b.c".to_owned()),
+        phone: None,
+        user_id: None,
+        coupon: Some("x".to_owned()),
+        gift_card: None,
+        street: None,
+        city: None,
+    });
+    assert!(ok.is_ok());
+    assert!(
+        AtLeast::validate(RawAtLeast {
+            a: None,
+            b: Some(1)
+        })
+        .is_ok()
+    );
+}
+
+#[test]
+fn test_presence_violated() {
+    let err = Contact::validate(RawContact {
+        email: Some("a@b.c".to_owned()),
+        phone: Some("123".to_owned()),
+        user_id: None,
+        coupon: Some("x".to_owned()),
+        gift_card: Some("y".to_owned()),
+        street: Some("Main St.".to_owned()),
+        city: None,
+    })
+    .unwrap_err();
+    assert_eq!(
+        paths(&err),
+        ["email", "phone", "coupon", "gift_card", "city"]
+    );
+    assert_eq!(
+        err.validates[0].message.as_deref(),
+        Some("exactly one of `email`, `phone`, `user_id` must be present")
+    );
+    assert_eq!(err.validates[4].expression, "all_or_none(street, city)");
+
+    let err = Contact::validate(RawContact {
+        email: None,
+        phone: None,
+        user_id: None,
+        coupon: None,
+        gift_card: None,
+        street: None,
+        city: None,
+    })
+    .unwrap_err();
+    assert_eq!(paths(&err), ["email", "phone", "user_id"]);
+
+    let err = AtLeast::validate(RawAtLeast { a: None, b: None }).unwrap_err();
+    assert_eq!(paths(&err), ["a", "b"]);
+}
+
+#[test]
+fn test_presence_labels() {
+    fn french(field: &str, _label: &str) -> Option<Cow<'static, str>> {
+        match field {
+            "fax" => Some("Télécopie".into()),
+            _ => None,
+        }
+    }
+
+    #[derive(Debug, Valust)]
+    #[valust(at_least_one_of(phone, fax))]
+    pub struct Reachable {
+        #[valust(label = "Phone number")]
+        phone: Option<String>,
+        fax: Option<String>,
+    }
+
+    let raw = || RawReachable {
+        phone: None,
+        fax: None,
+    };
+    let err = Reachable::validate(raw()).unwrap_err();
+    assert_eq!(
+        err.validates[0].message.as_deref(),
+        Some("at least one of Phone number, `fax` must be present")
+    );
+    assert_eq!(err.validates[0].label, "Phone number");
+
+    set_label_hook(Some(french));
+    let err = Reachable::validate(raw()).unwrap_err();
+    set_label_hook(None);
+    assert_eq!(
+        err.validates[1].message.as_deref(),
+        Some("at least one of Phone number, `Télécopie` must be present")
+    );
+    assert_eq!(err.validates[1].label, "Télécopie");
+}
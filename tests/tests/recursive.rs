@@ -0,0 +1,73 @@
+#![allow(dead_code)]
+
+use serde::Deserialize;
+use valust::{Raw, Validate};
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+#[forward_derive(Deserialize)]
+#[valust(max_depth = 3)]
+pub struct Comment {
+    #[valid(expr(!text.is_empty()))]
+    text: String,
+    #[forward]
+    replies: Vec<Comment>,
+}
+
+#[derive(Debug, Valust)]
+#[forward_derive(Deserialize)]
+pub struct Category {
+    #[valid(expr(!name.is_empty()))]
+    name: String,
+    #[forward]
+    parent: Option<Box<Category>>,
+}
+
+fn thread(depth: usize) -> RawComment {
+    let mut comment = RawComment {
+        text: format!("level {}", depth),
+        replies: vec![],
+    };
+    for level in (0..depth).rev() {
+        comment = RawComment {
+            text: format!("level {}", level),
+            replies: vec![comment],
+        };
+    }
+    comment
+}
+
+#[test]
+fn test_recursive() {
+    let text = r#"{
+        "text": "root",
+        "replies": [
+            { "text": "first", "replies": [] },
+            { "text": "", "replies": [{ "text": "", "replies": [] }] }
+        ]
+    }"#;
+    let raw: Raw<Comment> = serde_json::from_str(text).unwrap();
+    let err = Comment::validate(raw).unwrap_err();
+    let paths: Vec<_> = err.validates.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, ["replies.1.text", "replies.1.replies.0.text"]);
+
+    let text = r#"{ "name": "rust", "parent": { "name": "", "parent": null } }"#;
+    let raw: Raw<Category> = serde_json::from_str(text).unwrap();
+    let err = Category::validate(raw).unwrap_err();
+    assert_eq!(err.validates[0].path, "parent.name");
+
+    let text = r#"{ "name": "rust", "parent": { "name": "lang", "parent": null } }"#;
+    let raw: Raw<Category> = serde_json::from_str(text).unwrap();
+    let category = Category::validate(raw).unwrap();
+    assert_eq!(category.parent.unwrap().name, "lang");
+}
+
+#[test]
+fn test_max_depth() {
+    assert!(Comment::validate(thread(3)).is_ok());
+
+    let err = Comment::validate(thread(5)).unwrap_err();
+    assert_eq!(err.validates.len(), 1);
+    assert_eq!(err.validates[0].path, "replies.0.replies.0.replies.0.replies.0");
+    assert_eq!(err.validates[0].expression, "max_depth = 3");
+}
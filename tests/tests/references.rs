@@ -0,0 +1,133 @@
+#![allow(dead_code)]
+
+use serde::Deserialize;
+use valust::{Raw, Validate};
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+#[forward_derive(Deserialize)]
+pub struct Product {
+    #[valust(id)]
+    sku: String,
+    #[valid(expr(price > 0))]
+    price: u32,
+}
+
+#[derive(Debug, Valust)]
+#[forward_derive(Deserialize)]
+pub struct Order {
+    #[valust(id)]
+    id: u32,
+    #[valust(references = "products", label = "Product")]
+    sku: String,
+    #[valust(references = "products")]
+    extras: Vec<String>,
+}
+
+#[derive(Debug, Valust)]
+#[forward_derive(Deserialize)]
+pub struct Import {
+    #[forward]
+    products: Vec<Product>,
+    #[forward]
+    orders: Vec<Order>,
+}
+
+#[test]
+fn test_references() {
+    let text = r#"{
+        "products": [{ "sku": "a", "price": 1 }, { "sku": "b", "price": 2 }],
+        "orders": [
+            { "id": 1, "sku": "a", "extras": [] },
+            { "id": 2, "sku": "b", "extras": ["a", "b"] }
+        ]
+    }"#;
+    let raw: Raw<Import> = serde_json::from_str(text).unwrap();
+    assert!(Import::validate(raw).is_ok());
+}
+
+#[test]
+fn test_references_failed() {
+    let text = r#"{
+        "products": [{ "sku": "a", "price": 1 }, { "sku": "a", "price": 2 }],
+        "orders": [
+            { "id": 1, "sku": "c", "extras": [] },
+            { "id": 1, "sku": "a", "extras": ["a", "d"] }
+        ]
+    }"#;
+    let raw: Raw<Import> = serde_json::from_str(text).unwrap();
+    let err = Import::validate(raw).unwrap_err();
+
+    let errors: Vec<_> = err
+        .validates
+        .iter()
+        .map(|e| (e.path.as_str(), e.value.as_str()))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            ("products.1.sku", "a"),
+            ("orders.1.id", "1"),
+            ("orders.0.sku", "c"),
+            ("orders.1.extras", "d"),
+        ]
+    );
+    assert_eq!(
+        err.validates[0].message.as_deref(),
+        Some("`sku` is a duplicate id, first defined at `products.0.sku`")
+    );
+    assert_eq!(
+        err.validates[2].message.as_deref(),
+        Some("Product references an unknown id in `products`")
+    );
+}
+
+#[test]
+fn test_references_after_fields() {
+    // references are only checked once all fields are valid
+    let text = r#"{
+        "products": [{ "sku": "a", "price": 0 }],
+        "orders": [{ "id": 1, "sku": "c", "extras": [] }]
+    }"#;
+    let raw: Raw<Import> = serde_json::from_str(text).unwrap();
+    let err = Import::validate(raw).unwrap_err();
+    assert_eq!(err.validates.len(), 1);
+    assert_eq!(err.validates[0].path, "products.0.price");
+}
+
+#[test]
+fn test_references_root_vec() {
+    // the elements of a root `Vec` are checked together
+    let text = r#"[{ "sku": "a", "price": 1 }, { "sku": "b", "price": 2 }, { "sku": "a", "price": 3 }]"#;
+    let raw: Raw<Vec<Product>> = serde_json::from_str(text).unwrap();
+    let err = <Vec<Product>>::validate(raw).unwrap_err();
+    assert_eq!(err.validates.len(), 1);
+    assert_eq!(err.validates[0].path, "2.sku");
+    assert_eq!(
+        err.validates[0].message.as_deref(),
+        Some("`sku` is a duplicate id, first defined at `0.sku`")
+    );
+
+    let text = r#"[{ "id": 1, "sku": "a", "extras": [] }]"#;
+    let raw: Raw<Vec<Order>> = serde_json::from_str(text).unwrap();
+    let err = <Vec<Order>>::validate(raw).unwrap_err();
+    assert_eq!(err.validates[0].path, "0.sku");
+}
+
+#[test]
+fn test_has_refs() {
+    #[derive(Debug, Valust)]
+    pub struct Plain {
+        #[valid(expr(n > 0))]
+        n: u8,
+    }
+
+    #[derive(Debug, Valust)]
+    pub struct Wrapper {
+        #[forward]
+        plain: Vec<Plain>,
+    }
+
+    const _: () = assert!(Import::HAS_REFS && <Option<Box<Order>>>::HAS_REFS);
+    const _: () = assert!(!Plain::HAS_REFS && !Wrapper::HAS_REFS);
+}
@@ -2,6 +2,15 @@ use valust::error::display::ErrorDisplay;
 use valust::{Raw, Validate};
 use valust_derive::Valust;
 
+// outside of a test function, so that clippy checks the generated code
+// against the MSRV
+#[derive(Debug, Valust)]
+pub struct Username {
+    #[valid(regex(r"^[a-z][a-z0-9_]*$"))]
+    #[allow(unused)]
+    name: String,
+}
+
 #[test]
 fn test_regex() {
     #[derive(Debug, Valust)]
@@ -18,3 +27,43 @@ fn test_regex() {
     let valid = Test::validate(raw);
     println!("{}", valid.unwrap_err().full_stringify());
 }
+
+#[test]
+fn test_runtime_regex() {
+    const DATE: &str = r"^\d{4}-\d{2}-\d{2}$";
+    // e.g. loaded from a configuration file
+    fn broken() -> &'static str {
+        "[a-z"
+    }
+
+    #[derive(Debug, Valust)]
+    struct Test {
+        #[valid(regex(DATE))]
+        #[allow(unused)]
+        date: String,
+        #[valid(regex(broken()))]
+        #[allow(unused)]
+        name: String,
+    }
+
+    assert!(
+        Username::validate(RawUsername {
+            name: "ada_1".to_owned(),
+        })
+        .is_ok()
+    );
+
+    let err = Test::validate(Raw::<Test> {
+        date: "2021-01-0".to_string(),
+        name: "ada".to_string(),
+    })
+    .unwrap_err();
+    assert_eq!(err.validates.len(), 2);
+    assert_eq!(
+        err.validates[0].message.as_deref(),
+        Some("`date` does not match the regex")
+    );
+    // an invalid regex is reported with the error of the regex engine
+    // instead of panicking
+    assert!(err.validates[1].cause.is_some());
+}
@@ -0,0 +1,94 @@
+#![allow(dead_code)]
+
+use valust::Validate;
+use valust::error::transform::Rejected;
+use valust_derive::Valust;
+
+/// Neither `Clone` nor `Copy`.
+#[derive(Debug)]
+pub struct Ticket(String);
+
+fn redeem(t: Ticket) -> Result<u32, Rejected<Ticket, std::num::ParseIntError>> {
+    match t.0.parse() {
+        Ok(v) => Ok(v),
+        Err(cause) => Err(Rejected::new(t, cause)),
+    }
+}
+
+#[test]
+fn test_try_ret() {
+    #[derive(Debug, Valust)]
+    struct Data {
+        #[trans(func(Ticket => try_ret(redeem)))]
+        func: u32,
+        #[trans(expr(Ticket => try_ret(redeem(expr))))]
+        expr: u32,
+    }
+
+    let ok = Data::validate(RawData {
+        func: Ticket("1".to_owned()),
+        expr: Ticket("2".to_owned()),
+    })
+    .unwrap();
+    assert_eq!((ok.func, ok.expr), (1, 2));
+
+    let err = Data::validate(RawData {
+        func: Ticket("a".to_owned()),
+        expr: Ticket("b".to_owned()),
+    })
+    .unwrap_err();
+    assert_eq!(err.transforms[0].value, "(Ticket) Ticket(\"a\")");
+    assert_eq!(err.transforms[1].value, "(Ticket) Ticket(\"b\")");
+}
+
+#[test]
+fn test_try_without_clone() {
+    fn parse(t: Ticket) -> Result<u32, std::num::ParseIntError> {
+        t.0.parse()
+    }
+
+    #[derive(Debug, Valust)]
+    struct Data {
+        #[trans(func(Ticket => try(parse)))]
+        func: u32,
+        #[trans(func(String => try(|s: String| s.parse::<u8>())))]
+        long: u8,
+    }
+
+    let err = Data::validate(RawData {
+        func: Ticket("a".to_owned()),
+        long: "x".repeat(1000),
+    })
+    .unwrap_err();
+    assert_eq!(err.transforms[0].value, "(Ticket) Ticket(\"a\")");
+    // longer than the inline buffer, but not truncated without limits
+    let long = &err.transforms[1].value;
+    assert_eq!(*long, format!("(String) \"{}\"", "x".repeat(1000)));
+}
+
+#[test]
+fn test_try_limits() {
+    #[derive(Debug, Valust)]
+    struct Data {
+        #[valust(value(max_bytes = 600))]
+        #[valid(expr(valid.len() < 10))]
+        valid: String,
+        #[valust(value(max_bytes = 600))]
+        #[trans(func(String => try(|s: String| s.parse::<u8>())))]
+        parsed: u8,
+    }
+
+    let err = Data::validate(RawData {
+        valid: "x".repeat(1000),
+        parsed: "x".repeat(1000),
+    })
+    .unwrap_err();
+    // the same limits apply to the input of `try`, beyond the inline buffer
+    let (valid, parsed) = (&err.validates[0].value, &err.transforms[0].value);
+    assert_eq!(parsed, valid);
+    assert_eq!(
+        *parsed,
+        format!("(String) \"{}… (len = 1000)", "x".repeat(599))
+    );
+    assert!(parsed.len() > valust::error::value::SNAPSHOT_BYTES);
+}
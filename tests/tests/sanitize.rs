@@ -0,0 +1,68 @@
+#![allow(dead_code)]
+
+use valust::Validate;
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+pub struct Profile {
+    #[trans(trim, lowercase)]
+    email: String,
+    #[trans(collapse_whitespace, trim)]
+    name: String,
+    #[trans(trim, empty_to_none)]
+    bio: Option<String>,
+    #[trans(trim_start, uppercase)]
+    code: Option<String>,
+    #[trans(trim_end, strip_control_chars)]
+    note: String,
+    #[trans(trim)]
+    tags: Vec<String>,
+    #[trans(nfc)]
+    composed: String,
+    #[trans(nfkc)]
+    compatible: String,
+}
+
+fn empty() -> RawProfile {
+    RawProfile {
+        email: String::new(),
+        name: String::new(),
+        bio: String::new(),
+        code: None,
+        note: String::new(),
+        tags: Vec::new(),
+        composed: String::new(),
+        compatible: String::new(),
+    }
+}
+
+#[test]
+fn test_sanitize() {
+    let profile = Profile::validate(RawProfile {
+        email: "  Ada@Example.ORG\n".to_owned(),
+        name: " Ada \t  Lovelace ".to_owned(),
+        bio: "   ".to_owned(),
+        code: Some("  ab1 ".to_owned()),
+        note: "a\u{7}b\nc  ".to_owned(),
+        tags: vec![" a".to_owned(), "b ".to_owned()],
+        composed: "e\u{301}".to_owned(),
+        compatible: "\u{fb01}".to_owned(),
+    })
+    .unwrap();
+    assert_eq!(profile.email, "ada@example.org");
+    assert_eq!(profile.name, "Ada Lovelace");
+    assert_eq!(profile.bio, None);
+    assert_eq!(profile.code.as_deref(), Some("AB1 "));
+    assert_eq!(profile.note, "ab\nc");
+    assert_eq!(profile.tags, ["a", "b"]);
+    assert_eq!(profile.composed, "\u{e9}");
+    assert_eq!(profile.compatible, "fi");
+
+    let profile = Profile::validate(RawProfile {
+        bio: " Mathematician ".to_owned(),
+        ..empty()
+    })
+    .unwrap();
+    assert_eq!(profile.bio.as_deref(), Some("Mathematician"));
+    assert_eq!(profile.code, None);
+}
@@ -0,0 +1,119 @@
+#![allow(dead_code)]
+
+use std::fmt;
+
+use valust::Validate;
+use valust::transformer::{Lowercase, Transformer, Trim, parse};
+use valust_derive::Valust;
+
+#[derive(Debug, PartialEq)]
+pub struct Email(String);
+
+#[derive(Debug)]
+pub struct InvalidEmail;
+
+impl fmt::Display for InvalidEmail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("missing `@`")
+    }
+}
+
+pub struct ParseEmail;
+
+impl Transformer<String, Email> for ParseEmail {
+    type Error = InvalidEmail;
+
+    fn transform(&self, input: String) -> Result<Email, InvalidEmail> {
+        if input.contains('@') {
+            Ok(Email(input))
+        } else {
+            Err(InvalidEmail)
+        }
+    }
+}
+
+#[derive(Debug, Valust)]
+pub struct Signup {
+    #[trans(with(String => Trim.then(Lowercase).then(ParseEmail)))]
+    email: Email,
+    #[trans(with(Option<String> => Trim.then(parse::<u8>()).optional()))]
+    age: Option<u8>,
+    #[trans(with(String => parse::<u16>().or_else(|s: String| match s.as_str() {
+        "http" => Ok(80),
+        _ => s.parse(),
+    }), "{label} is not a port"))]
+    port: u16,
+}
+
+fn valid() -> RawSignup {
+    RawSignup {
+        email: " Ada@Example.org ".to_owned(),
+        age: Some(" 36".to_owned()),
+        port: "http".to_owned(),
+    }
+}
+
+#[test]
+fn test_with() {
+    let signup = Signup::validate(valid()).unwrap();
+    assert_eq!(signup.email, Email("ada@example.org".to_owned()));
+    assert_eq!(signup.age, Some(36));
+    assert_eq!(signup.port, 80);
+
+    let signup = Signup::validate(RawSignup {
+        age: None,
+        ..valid()
+    })
+    .unwrap();
+    assert_eq!(signup.age, None);
+
+    let err = Signup::validate(RawSignup {
+        email: "ada".to_owned(),
+        age: Some("old".to_owned()),
+        port: "ftp".to_owned(),
+    })
+    .unwrap_err();
+    let causes: Vec<_> = err.transforms.iter().map(|e| e.cause.to_string()).collect();
+    assert_eq!(
+        causes,
+        [
+            "missing `@`",
+            "invalid digit found in string",
+            "invalid digit found in string"
+        ]
+    );
+    let types: Vec<_> = err
+        .transforms
+        .iter()
+        .map(|e| (e.source_type_name, e.target_type_name))
+        .collect();
+    assert_eq!(
+        types,
+        [
+            ("alloc::string::String", "transformer::Email"),
+            (
+                "core::option::Option<alloc::string::String>",
+                "core::option::Option<u8>"
+            ),
+            ("alloc::string::String", "u16"),
+        ]
+    );
+    assert_eq!(err.transforms[0].message, None);
+    assert_eq!(
+        err.transforms[2].message.as_deref(),
+        Some("port is not a port")
+    );
+    assert_eq!(
+        err.transforms[0].expression,
+        "with(Trim.then(Lowercase).then(ParseEmail))"
+    );
+}
+
+#[test]
+fn test_combinators() {
+    let number = parse::<i32>().map_err(|_| "not a number");
+    assert_eq!(
+        number.transform("x".to_owned()).unwrap_err(),
+        "not a number"
+    );
+}
@@ -0,0 +1,104 @@
+#![allow(dead_code)]
+
+use valust::Validate;
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+pub struct Profile {
+    #[valid(char_len(min = 2, max = 5), no_whitespace_edges)]
+    name: String,
+    #[valid(grapheme_len(max = 3))]
+    reaction: String,
+    #[valid(ascii, alphanumeric)]
+    handle: String,
+    #[valid(printable)]
+    title: String,
+    #[valid(no_control_chars)]
+    bio: String,
+}
+
+fn valid() -> RawProfile {
+    RawProfile {
+        name: "李小龍".to_owned(),
+        reaction: "👍🏽👨‍👩‍👧".to_owned(),
+        handle: "bruce42".to_owned(),
+        title: "Mr. Lee".to_owned(),
+        bio: "line one\n\tline two".to_owned(),
+    }
+}
+
+#[test]
+fn test_unicode_len() {
+    // 9 bytes, 3 chars
+    assert!(Profile::validate(valid()).is_ok());
+
+    let err = Profile::validate(RawProfile {
+        name: "李".to_owned(),
+        reaction: "👍🏽👍🏽👍🏽👍🏽".to_owned(),
+        ..valid()
+    })
+    .unwrap_err();
+    let messages: Vec<_> = err
+        .validates
+        .iter()
+        .map(|e| e.message.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "`name` must be between 2 and 5 characters long",
+            "`reaction` must be at most 3 characters long",
+        ]
+    );
+}
+
+#[test]
+fn test_char_classes() {
+    let err = Profile::validate(RawProfile {
+        name: " Lee".to_owned(),
+        handle: "brüce_42".to_owned(),
+        title: "Mr.\nLee".to_owned(),
+        bio: "bell\u{7}".to_owned(),
+        ..valid()
+    })
+    .unwrap_err();
+    let errors: Vec<_> = err
+        .validates
+        .iter()
+        .map(|e| (e.path.as_str(), e.expression))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            ("name", "no_whitespace_edges"),
+            ("handle", "ascii"),
+            ("title", "printable"),
+            ("bio", "no_control_chars"),
+        ]
+    );
+}
+
+#[test]
+fn test_printable() {
+    // zero-width space, right-to-left override, private-use, unassigned
+    for title in [
+        "Mr.\u{200B}Lee",
+        "Mr.\u{202E}eeL",
+        "Mr.\u{E000}",
+        "Mr.\u{378}",
+    ] {
+        let err = Profile::validate(RawProfile {
+            title: title.to_owned(),
+            ..valid()
+        })
+        .unwrap_err();
+        assert_eq!(err.validates[0].expression, "printable", "{:?}", title);
+    }
+    assert!(
+        Profile::validate(RawProfile {
+            title: "Dr. Lee — 李 ✓".to_owned(),
+            ..valid()
+        })
+        .is_ok()
+    );
+}
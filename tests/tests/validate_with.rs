@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+
+use valust::Validate;
+use valust::error::ValidationError;
+use valust::error::validate::ValidateError;
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+#[forward_derive(Debug, Clone)]
+pub struct Line {
+    #[valid(expr(quantity > 0))]
+    quantity: u32,
+    unit_price: u32,
+    total: u32,
+}
+
+#[derive(Debug, Valust)]
+#[forward_derive(Debug, Clone)]
+#[valust(pre_validate_with = Self::check_raw)]
+#[valust(validate_with = Self::check_totals)]
+pub struct Invoice {
+    #[valid(expr(!lines.is_empty()))]
+    lines: Vec<RawLine>,
+    total: u32,
+}
+
+impl Invoice {
+    fn check_raw(raw: &RawInvoice, err: &mut ValidationError) {
+        if raw.lines.len() > 100 {
+            err.push_validate_error(ValidateError::custom("lines", "too many lines"));
+        }
+    }
+
+    fn check_totals(&self, err: &mut ValidationError) {
+        let mut sum = 0;
+        for (idx, line) in self.lines.iter().enumerate() {
+            if line.quantity * line.unit_price != line.total {
+                err.push_validate_error(
+                    ValidateError::custom("lines", "line total does not match")
+                        .with_path(format!("lines.{}.total", idx)),
+                );
+            }
+            sum += line.total;
+        }
+        if sum != self.total {
+            err.push_validate_error(ValidateError::custom(
+                "total",
+                "invoice total does not match",
+            ));
+        }
+    }
+}
+
+fn line(quantity: u32, unit_price: u32, total: u32) -> RawLine {
+    RawLine {
+        quantity,
+        unit_price,
+        total,
+    }
+}
+
+#[test]
+fn test_validate_with() {
+    let ok = Invoice::validate(RawInvoice {
+        lines: vec![line(2, 5, 10), line(1, 3, 3)],
+        total: 13,
+    });
+    assert!(ok.is_ok());
+
+    let err = Invoice::validate(RawInvoice {
+        lines: vec![line(2, 5, 10), line(1, 3, 4), line(3, 1, 1)],
+        total: 10,
+    })
+    .unwrap_err();
+    let paths: Vec<_> = err.validates.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, ["lines.1.total", "lines.2.total", "total"]);
+    assert_eq!(
+        err.validates[2].message.as_deref(),
+        Some("invoice total does not match")
+    );
+}
+
+#[test]
+fn test_pre_validate_with() {
+    let err = Invoice::validate(RawInvoice {
+        lines: vec![line(1, 1, 1); 101],
+        total: 0,
+    })
+    .unwrap_err();
+    assert_eq!(err.len(), 1);
+    assert_eq!(err.validates[0].message.as_deref(), Some("too many lines"));
+}
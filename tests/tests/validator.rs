@@ -0,0 +1,138 @@
+#![allow(dead_code)]
+
+use valust::Validate;
+use valust::validator::{Ascii, Len, NonEmpty, Range, Validator, predicate};
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+pub struct Account {
+    #[valid(with(Len::max(8).and(Ascii)))]
+    handle: String,
+    #[valust(label = "Age")]
+    #[valid(with(Range::between(13, 130)))]
+    age: u32,
+    #[valid(with(Len::between(1, 3).each().and(NonEmpty)))]
+    tags: Vec<String>,
+    #[valid(with(predicate("even", "{label} must be even", |x: &i64| x % 2 == 0).not()))]
+    odd: i64,
+}
+
+fn valid() -> RawAccount {
+    RawAccount {
+        handle: "ada".to_owned(),
+        age: 36,
+        tags: vec!["a".to_owned(), "abc".to_owned()],
+        odd: 3,
+    }
+}
+
+#[test]
+fn test_with() {
+    assert!(Account::validate(valid()).is_ok());
+
+    let err = Account::validate(RawAccount {
+        handle: "adalovelace".to_owned(),
+        age: 7,
+        tags: vec!["a".to_owned(), "abcd".to_owned()],
+        odd: 4,
+    })
+    .unwrap_err();
+    let messages: Vec<_> = err
+        .validates
+        .iter()
+        .map(|e| e.message.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "`handle` must have a length of at most 8",
+            "Age must be between 13 and 130",
+            "`tags` must have a length between 1 and 3 (item 1)",
+            "`odd` must not be even",
+        ]
+    );
+    let codes: Vec<_> = err
+        .validates
+        .iter()
+        .map(|e| e.code.as_deref().unwrap())
+        .collect();
+    assert_eq!(codes, ["len", "range", "len", "not_even"]);
+    assert_eq!(
+        err.validates[2].params,
+        [
+            ("min", "1".to_owned()),
+            ("max", "3".to_owned()),
+            ("index", "1".to_owned())
+        ]
+    );
+    assert_eq!(
+        err.validates[0].expression,
+        "with(Len :: max(8).and(Ascii))"
+    );
+
+    let err = Account::validate(RawAccount {
+        handle: "ádá".to_owned(),
+        tags: vec![],
+        ..valid()
+    })
+    .unwrap_err();
+    let codes: Vec<_> = err
+        .validates
+        .iter()
+        .map(|e| e.code.as_deref().unwrap())
+        .collect();
+    assert_eq!(codes, ["ascii", "non_empty"]);
+}
+
+#[test]
+fn test_combinators() {
+    let rule = Len::exactly(2).or(Ascii);
+    assert!(rule.check("ab").is_ok());
+    assert!(rule.check("é").is_ok());
+    let violation = rule.check("ééé").unwrap_err();
+    assert_eq!(violation.code, "len_or_ascii");
+    assert_eq!(
+        violation.message,
+        "{label} must have a length of {len}, or {label} must only contain ASCII characters"
+    );
+
+    let rule = Range::min(0).not();
+    assert!(rule.check(&-1).is_ok());
+    let violation = rule.check(&1).unwrap_err();
+    assert_eq!(violation.code, "not_range");
+    assert_eq!(violation.message, "{label} must not be at least {min}");
+    let violation = NonEmpty.not().check("a").unwrap_err();
+    assert_eq!(violation.message, "{label} must be empty");
+
+    // negated combinators negate each of their rules
+    let rule = Len::max(3).or(Ascii).not();
+    let violation = rule.check("abc").unwrap_err();
+    assert_eq!(violation.code, "not_len_and_not_ascii");
+    assert_eq!(
+        violation.message,
+        "{label} must not have a length of at most {max}, and {label} must not only contain ASCII characters"
+    );
+    assert!(rule.check("ééé").is_ok());
+
+    let rule = Len::max(3).and(Ascii);
+    let violation = rule.violation();
+    assert_eq!(violation.code, "len_and_ascii");
+    assert_eq!(
+        violation.message,
+        "{label} must have a length of at most {max}, and {label} must only contain ASCII characters"
+    );
+    let rule = rule.not();
+    let violation = rule.check("abc").unwrap_err();
+    assert_eq!(violation.code, "not_len_or_not_ascii");
+    assert_eq!(
+        violation.message,
+        "{label} must not have a length of at most {max}, or {label} must not only contain ASCII characters"
+    );
+    assert!(rule.check("abcd").is_ok());
+    assert_eq!(rule.not().check("abcd").unwrap_err().code, "len_and_ascii");
+
+    // validators are reusable by reference
+    let short = Len::max(2);
+    assert!((&short).and(NonEmpty).check(&vec![1]).is_ok());
+    assert!(short.each().check(&vec!["ab", "abc"][..]).is_err());
+}
@@ -0,0 +1,193 @@
+#![allow(dead_code)]
+// the generated code does not silence these lints in user code
+#![deny(unused_mut, unused_imports)]
+
+use std::fmt;
+
+use valust::Validate;
+use valust::error::value::{ValueLimits, capture_value};
+use valust_derive::Valust;
+
+/// A third-party type with neither `Debug` nor `Clone`.
+pub struct Opaque(u32);
+
+/// A third-party type with `Display` only.
+pub struct Shown(u32);
+
+impl fmt::Display for Shown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "shown#{}", self.0)
+    }
+}
+
+fn redact(_: &String) -> &'static str {
+    "<redacted>"
+}
+
+#[test]
+fn test_value_none() {
+    #[derive(Valust)]
+    struct Data {
+        #[valust(value(none))]
+        #[valid(expr(opaque.0 > 10))]
+        opaque: Opaque,
+        #[valust(value(none))]
+        #[trans(func(Opaque => try(|o: Opaque| u8::try_from(o.0))))]
+        small: u8,
+    }
+
+    let err = Data::validate(RawData {
+        opaque: Opaque(1),
+        small: Opaque(1000),
+    })
+    .map(|_| ())
+    .unwrap_err();
+    assert_eq!(err.validates[0].value, "(Opaque)");
+    assert_eq!(err.transforms[0].value, "(Opaque)");
+}
+
+#[test]
+fn test_value_display_and_with() {
+    #[derive(Valust)]
+    #[valust(value(display))]
+    struct Data {
+        #[valid(expr(shown.0 > 10))]
+        shown: Shown,
+        #[valust(value(with = redact))]
+        #[valid(expr(secret.len() > 8))]
+        secret: String,
+    }
+
+    let err = Data::validate(RawData {
+        shown: Shown(1),
+        secret: "short".to_owned(),
+    })
+    .map(|_| ())
+    .unwrap_err();
+    assert_eq!(err.validates[0].value, "(Shown) shown#1");
+    assert_eq!(err.validates[1].value, "(String) <redacted>");
+}
+
+#[test]
+fn test_value_limits() {
+    #[derive(Valust)]
+    #[valust(value(max_items = 3))]
+    struct Upload {
+        #[valid(expr(bytes.is_empty()))]
+        bytes: Vec<u8>,
+        #[valust(value(max_chars = 8))]
+        #[valid(expr(text.is_empty()))]
+        text: String,
+        #[valid(expr(nested.is_empty()))]
+        nested: Vec<(String, Vec<u8>)>,
+    }
+
+    let err = Upload::validate(RawUpload {
+        bytes: vec![0; 50_000],
+        text: "a, b, [c]".repeat(1000),
+        nested: vec![("x, y".to_owned(), vec![1, 2, 3, 4]); 2],
+    })
+    .map(|_| ())
+    .unwrap_err();
+    assert_eq!(
+        err.validates[0].value,
+        "(Vec < u8 >) [0, 0, 0, …] (len = 50000)"
+    );
+    assert_eq!(err.validates[1].value, "(String) \"a, b, [… (len = 9000)");
+    assert_eq!(
+        err.validates[2].value,
+        "(Vec < (String, Vec < u8 >) >) [(\"x, y\", [1, 2, 3, …]), (\"x, y\", [1, 2, 3, …])] (len = 2)"
+    );
+}
+
+fn summarize(value: fmt::Arguments<'_>, limits: ValueLimits) -> String {
+    capture_value("T", value, limits, None)
+}
+
+#[test]
+fn test_value_limits_structs() {
+    #[derive(Debug)]
+    struct Foo {
+        a: u8,
+        b: u8,
+        c: u8,
+    }
+
+    let limits = ValueLimits {
+        max_items: Some(1),
+        ..ValueLimits::UNLIMITED
+    };
+    // fields of structs and tuples are not elements
+    let foo = Foo { a: 1, b: 2, c: 3 };
+    assert_eq!(
+        summarize(format_args!("{:?}", foo), limits),
+        "(T) Foo { a: 1, b: 2, c: 3 }"
+    );
+    assert_eq!(
+        summarize(format_args!("{:?}", (1, 2, 3)), limits),
+        "(T) (1, 2, 3)"
+    );
+    assert_eq!(
+        summarize(format_args!("{:?}", vec![(1, 2), (3, 4)]), limits),
+        "(T) [(1, 2), …] (truncated)"
+    );
+}
+
+#[test]
+fn test_value_limits_zero_items() {
+    let limits = ValueLimits {
+        max_items: Some(0),
+        ..ValueLimits::UNLIMITED
+    };
+    assert_eq!(
+        summarize(format_args!("{:?}", vec![1, 2, 3]), limits),
+        "(T) […] (truncated)"
+    );
+    assert_eq!(
+        summarize(format_args!("{:?}", vec![vec![1], vec![2]]), limits),
+        "(T) […] (truncated)"
+    );
+    assert_eq!(
+        summarize(format_args!("{:?}", Vec::<u8>::new()), limits),
+        "(T) []"
+    );
+}
+
+#[test]
+fn test_value_limits_apostrophe() {
+    let limits = ValueLimits {
+        max_items: Some(1),
+        ..ValueLimits::UNLIMITED
+    };
+    // an apostrophe is not a char literal
+    assert_eq!(
+        summarize(format_args!("O'Brien [1, 2, 3]"), limits),
+        "(T) O'Brien [1, …] (truncated)"
+    );
+    assert_eq!(
+        summarize(format_args!("rock 'n roll, [1, 2, 3]"), limits),
+        "(T) rock 'n roll, [1, …] (truncated)"
+    );
+    assert_eq!(
+        summarize(format_args!("{:?}", vec!['\'', ',', '[']), limits),
+        "(T) ['\\'', …] (truncated)"
+    );
+}
+
+#[test]
+fn test_value_limits_bytes() {
+    #[derive(Valust)]
+    struct Data {
+        #[valust(value(max_bytes = 6))]
+        #[valid(expr(text.is_empty()))]
+        text: String,
+    }
+
+    let err = Data::validate(RawData {
+        text: "héllo wörld".to_owned(),
+    })
+    .map(|_| ())
+    .unwrap_err();
+    // `é` takes 2 bytes, and is never cut
+    assert_eq!(err.validates[0].value, "(String) \"héll… (len = 13)");
+}
@@ -0,0 +1,71 @@
+#![allow(dead_code)]
+
+use serde::Deserialize;
+use valust::Validate;
+use valust::error::enums::ParseEnumError;
+use valust_derive::{Valust, ValustEnum};
+use valust_utils::convert::parse_to;
+
+#[derive(Debug, PartialEq, ValustEnum)]
+#[valust(case_insensitive, rename_all = "snake_case")]
+pub enum Status {
+    Draft,
+    #[valust(alias = "live")]
+    Published,
+    InReview,
+}
+
+#[derive(Debug, PartialEq, Deserialize, ValustEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Channel {
+    #[serde(alias = "mail")]
+    EMail,
+    #[serde(rename = "sms")]
+    TextMessage,
+    PushNotification,
+}
+
+#[derive(Debug, Valust)]
+pub struct Post {
+    #[trans(func(String => try(parse_to::<Status>)))]
+    status: Status,
+}
+
+#[test]
+fn test_valust_enum() {
+    assert_eq!("draft".parse(), Ok(Status::Draft));
+    assert_eq!("IN_REVIEW".parse(), Ok(Status::InReview));
+    assert_eq!("Live".parse(), Ok(Status::Published));
+
+    assert_eq!("e-mail".parse(), Ok(Channel::EMail));
+    assert_eq!("mail".parse(), Ok(Channel::EMail));
+    assert_eq!("sms".parse(), Ok(Channel::TextMessage));
+    assert_eq!("push-notification".parse(), Ok(Channel::PushNotification));
+    assert!("SMS".parse::<Channel>().is_err());
+
+    // the same names as serde
+    let channel: Channel = serde_json::from_str(r#""push-notification""#).unwrap();
+    assert_eq!(channel, Channel::PushNotification);
+}
+
+#[test]
+fn test_valust_enum_error() {
+    let err = "deleted".parse::<Status>().unwrap_err();
+    assert_eq!(
+        err,
+        ParseEnumError {
+            enum_name: "Status",
+            input: "deleted".to_owned(),
+            expected: &["draft", "published", "in_review"],
+        }
+    );
+
+    let err = Post::validate(RawPost {
+        status: "deleted".to_owned(),
+    })
+    .unwrap_err();
+    assert_eq!(
+        err.transforms[0].cause.to_string(),
+        "unknown `Status` variant `deleted`, expected one of `draft`, `published`, `in_review`"
+    );
+}
@@ -0,0 +1,63 @@
+#![allow(dead_code)]
+
+use serde::Deserialize;
+use valust::{Raw, Validate};
+use valust_derive::Valust;
+
+#[derive(Debug, Valust)]
+#[forward_derive(Deserialize)]
+#[forward_attr(serde(rename_all = "camelCase"))]
+pub struct Address {
+    #[valid(expr(!zip_code.is_empty()))]
+    zip_code: String,
+}
+
+#[derive(Debug, Valust)]
+#[forward_derive(Deserialize)]
+#[forward_attr(serde(rename_all = "camelCase"))]
+pub struct User {
+    #[valid(expr(user_id > 0))]
+    user_id: u32,
+    #[forward_attr(serde(rename = "mail"))]
+    #[valid(expr(email.contains('@')))]
+    email: String,
+    #[forward_attr(serde(rename(serialize = "ignored", deserialize = "phoneNo")))]
+    #[valid(expr(!phone.is_empty()))]
+    phone: String,
+    #[valust(rename = "nick")]
+    #[forward_attr(serde(rename = "nick"))]
+    #[trans(func(String => try(|s: String| s.parse::<u8>())))]
+    nickname_len: u8,
+    #[forward]
+    home_address: Address,
+}
+
+#[test]
+fn test_wire_names() {
+    let text = r#"{
+        "userId": 0,
+        "mail": "nope",
+        "phoneNo": "",
+        "nick": "x",
+        "homeAddress": { "zipCode": "" }
+    }"#;
+    let raw: Raw<User> = serde_json::from_str(text).unwrap();
+    let err = User::validate(raw).unwrap_err();
+
+    let paths: Vec<_> = err
+        .validates
+        .iter()
+        .map(|e| (e.field, e.path.as_str()))
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            ("userId", "userId"),
+            ("mail", "mail"),
+            ("phoneNo", "phoneNo"),
+            ("zipCode", "homeAddress.zipCode"),
+        ]
+    );
+    assert_eq!(err.transforms[0].field, "nick");
+    assert_eq!(err.transforms[0].path, "nick");
+}